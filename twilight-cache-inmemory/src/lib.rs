@@ -1053,7 +1053,7 @@ mod tests {
     use crate::{test, DefaultInMemoryCache};
     use twilight_model::{
         gateway::payload::incoming::RoleDelete,
-        guild::{Member, MemberFlags, Permissions, Role, RoleFlags},
+        guild::{Member, MemberFlags, Permissions, Role, RoleColors, RoleFlags},
         id::Id,
         util::Timestamp,
     };
@@ -1106,6 +1106,7 @@ mod tests {
             vec![
                 Role {
                     color: 0,
+                    colors: RoleColors::solid(0),
                     hoist: false,
                     icon: None,
                     id: Id::new(1),
@@ -1120,6 +1121,7 @@ mod tests {
                 },
                 Role {
                     color: 0,
+                    colors: RoleColors::solid(0),
                     hoist: false,
                     icon: None,
                     id: Id::new(2),
@@ -14,8 +14,8 @@ use twilight_model::{
     guild::{
         scheduled_event::{EntityType, GuildScheduledEvent, PrivacyLevel, Status},
         AfkTimeout, DefaultMessageNotificationLevel, Emoji, ExplicitContentFilter, Guild, Member,
-        MemberFlags, MfaLevel, NSFWLevel, PartialMember, Permissions, PremiumTier, Role, RoleFlags,
-        SystemChannelFlags, VerificationLevel,
+        MemberFlags, MfaLevel, NSFWLevel, PartialMember, Permissions, PremiumTier, Role,
+        RoleColors, RoleFlags, SystemChannelFlags, VerificationLevel,
     },
     id::{
         marker::{
@@ -312,6 +312,7 @@ pub fn member(id: Id<UserMarker>) -> Member {
 pub fn role(id: Id<RoleMarker>) -> Role {
     Role {
         color: 0,
+        colors: RoleColors::solid(0),
         hoist: false,
         icon: None,
         id,
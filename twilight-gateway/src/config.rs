@@ -3,7 +3,9 @@
 use crate::{queue::InMemoryQueue, Session};
 use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
+    num::NonZeroU8,
     sync::Arc,
+    time::Duration,
 };
 use tokio_websockets::Connector;
 use twilight_model::gateway::{
@@ -40,6 +42,9 @@ impl Debug for Token {
 /// [`From<Config>`] implementation and then rebuilding it into a rew config.
 #[derive(Clone, Debug)]
 pub struct Config<Q = InMemoryQueue> {
+    /// HTTP proxy to tunnel gateway connections through.
+    #[cfg(feature = "http-proxy")]
+    http_proxy: Option<Box<str>>,
     /// Identification properties the shard will use.
     identify_properties: Option<IdentifyProperties>,
     /// Intents that the shard requests when identifying with the gateway.
@@ -47,6 +52,10 @@ pub struct Config<Q = InMemoryQueue> {
     /// When the gateway will stop sending a guild's member list in
     /// Guild Create events.
     large_threshold: u64,
+    /// Maximum number of consecutive failed connection attempts.
+    max_reconnect_attempts: Option<NonZeroU8>,
+    /// Maximum delay between reconnection attempts.
+    max_reconnect_delay: Duration,
     /// Presence to set when identifying with the gateway.
     presence: Option<UpdatePresencePayload>,
     /// Gateway proxy URL.
@@ -84,6 +93,13 @@ impl Config {
 }
 
 impl<Q> Config<Q> {
+    /// Immutable reference to the HTTP proxy URL gateway connections are
+    /// tunneled through.
+    #[cfg(feature = "http-proxy")]
+    pub fn http_proxy(&self) -> Option<&str> {
+        self.http_proxy.as_deref()
+    }
+
     /// Immutable reference to the identification properties the shard will use.
     pub const fn identify_properties(&self) -> Option<&IdentifyProperties> {
         self.identify_properties.as_ref()
@@ -100,6 +116,16 @@ impl<Q> Config<Q> {
         self.large_threshold
     }
 
+    /// Maximum number of consecutive failed connection attempts, if limited.
+    pub const fn max_reconnect_attempts(&self) -> Option<NonZeroU8> {
+        self.max_reconnect_attempts
+    }
+
+    /// Maximum delay between reconnection attempts.
+    pub const fn max_reconnect_delay(&self) -> Duration {
+        self.max_reconnect_delay
+    }
+
     /// Immutable reference to the presence to set when identifying
     /// with the gateway.
     ///
@@ -166,9 +192,13 @@ impl ConfigBuilder {
 
         Self {
             inner: Config {
+                #[cfg(feature = "http-proxy")]
+                http_proxy: None,
                 identify_properties: None,
                 intents,
                 large_threshold: 50,
+                max_reconnect_attempts: None,
+                max_reconnect_delay: Duration::from_secs(128),
                 presence: None,
                 proxy_url: None,
                 queue: InMemoryQueue::default(),
@@ -189,6 +219,41 @@ impl<Q> ConfigBuilder<Q> {
         self.inner
     }
 
+    /// Set the URL of an HTTP proxy to tunnel gateway connections through.
+    ///
+    /// The proxy is asked to open a tunnel to the gateway with a `CONNECT`
+    /// request. The URL is in the `http://[user:password@]host[:port]` format,
+    /// and credentials are sent using basic authentication.
+    ///
+    /// Only HTTP proxies are supported: SOCKS proxies and proxies that are
+    /// themselves connected to over TLS (`https`) are not.
+    ///
+    /// Unlike [`proxy_url`], which replaces the gateway with a gateway proxy
+    /// speaking the gateway protocol, this still connects to the gateway (or
+    /// the gateway proxy, if both are set) and only forwards the TCP
+    /// connection through the HTTP proxy.
+    ///
+    /// Default is to connect directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the URL isn't a valid `http` URL, such as one with another
+    /// scheme, without a host, or with a non-numeric port.
+    ///
+    /// [`proxy_url`]: Self::proxy_url
+    #[cfg(feature = "http-proxy")]
+    #[track_caller]
+    pub fn http_proxy(mut self, http_proxy: String) -> Self {
+        assert!(
+            crate::http_proxy::is_valid(&http_proxy),
+            "HTTP proxy URL isn't a valid http URL"
+        );
+
+        self.inner.http_proxy = Some(http_proxy.into_boxed_str());
+
+        self
+    }
+
     /// Set the properties to identify with.
     ///
     /// This may be used if you want to set a different operating system, for
@@ -251,6 +316,35 @@ impl<Q> ConfigBuilder<Q> {
         self
     }
 
+    /// Set the maximum number of consecutive failed connection attempts.
+    ///
+    /// Once this many attempts in a row have failed, the shard stops
+    /// reconnecting and returns a [`ReconnectExhausted`] error, after which it
+    /// is [fatally closed]. The count is reset once a connection succeeds.
+    ///
+    /// Default is to never stop reconnecting.
+    ///
+    /// [`ReconnectExhausted`]: crate::error::ReceiveMessageErrorType::ReconnectExhausted
+    /// [fatally closed]: crate::ShardState::FatallyClosed
+    pub const fn max_reconnect_attempts(mut self, max_reconnect_attempts: NonZeroU8) -> Self {
+        self.inner.max_reconnect_attempts = Some(max_reconnect_attempts);
+
+        self
+    }
+
+    /// Set the maximum delay between reconnection attempts.
+    ///
+    /// Shards wait before connecting to the gateway, starting at one second
+    /// and doubling with each consecutive failed attempt until this maximum is
+    /// reached. The delay is reset once a connection succeeds.
+    ///
+    /// Default value is 128 seconds.
+    pub const fn max_reconnect_delay(mut self, max_reconnect_delay: Duration) -> Self {
+        self.inner.max_reconnect_delay = max_reconnect_delay;
+
+        self
+    }
+
     /// Set the presence to use automatically when starting a new session.
     ///
     /// The active presence of a session is maintained across re-connections
@@ -306,6 +400,9 @@ impl<Q> ConfigBuilder<Q> {
     ///
     /// Resumes are always done to the URL specified in [`resume_gateway_url`].
     ///
+    /// To instead tunnel connections to the gateway through an HTTP proxy, see
+    /// `http_proxy`, which requires the `http-proxy` feature.
+    ///
     /// [`resume_gateway_url`]: twilight_model::gateway::payload::incoming::Ready::resume_gateway_url
     #[allow(clippy::missing_const_for_fn)]
     pub fn proxy_url(mut self, proxy_url: String) -> Self {
@@ -322,9 +419,13 @@ impl<Q> ConfigBuilder<Q> {
     /// turns itself into a no-op.
     pub fn queue<NewQ>(self, queue: NewQ) -> ConfigBuilder<NewQ> {
         let Config {
+            #[cfg(feature = "http-proxy")]
+            http_proxy,
             identify_properties,
             intents,
             large_threshold,
+            max_reconnect_attempts,
+            max_reconnect_delay,
             presence,
             proxy_url,
             queue: _,
@@ -337,9 +438,13 @@ impl<Q> ConfigBuilder<Q> {
 
         ConfigBuilder {
             inner: Config {
+                #[cfg(feature = "http-proxy")]
+                http_proxy,
                 identify_properties,
                 intents,
                 large_threshold,
+                max_reconnect_attempts,
+                max_reconnect_delay,
                 presence,
                 proxy_url,
                 queue,
@@ -402,7 +507,7 @@ impl<Q> From<Config<Q>> for ConfigBuilder<Q> {
 mod tests {
     use super::{Config, ConfigBuilder};
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{fmt::Debug, num::NonZeroU8, time::Duration};
     use twilight_model::gateway::Intents;
 
     assert_impl_all!(Config: Clone, Debug, Send, Sync);
@@ -412,6 +517,26 @@ mod tests {
         ConfigBuilder::new("test".to_owned(), Intents::empty())
     }
 
+    #[cfg(feature = "http-proxy")]
+    #[tokio::test]
+    async fn http_proxy() {
+        assert!(builder().build().http_proxy().is_none());
+        assert_eq!(
+            builder()
+                .http_proxy("http://proxy.local:3128".to_owned())
+                .build()
+                .http_proxy(),
+            Some("http://proxy.local:3128")
+        );
+    }
+
+    #[cfg(feature = "http-proxy")]
+    #[should_panic(expected = "HTTP proxy URL isn't a valid http URL")]
+    #[tokio::test]
+    async fn http_proxy_invalid() {
+        drop(builder().http_proxy("proxy.local:3128".to_owned()));
+    }
+
     #[tokio::test]
     async fn large_threshold() {
         const INPUTS: &[u64] = &[50, 100, 150, 200, 250];
@@ -436,6 +561,33 @@ mod tests {
         drop(builder().large_threshold(251));
     }
 
+    #[tokio::test]
+    async fn max_reconnect_attempts() {
+        assert!(builder().build().max_reconnect_attempts().is_none());
+        assert_eq!(
+            builder()
+                .max_reconnect_attempts(NonZeroU8::new(2).unwrap())
+                .build()
+                .max_reconnect_attempts(),
+            NonZeroU8::new(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn max_reconnect_delay() {
+        assert_eq!(
+            builder().build().max_reconnect_delay(),
+            Duration::from_secs(128)
+        );
+        assert_eq!(
+            builder()
+                .max_reconnect_delay(Duration::from_secs(30))
+                .build()
+                .max_reconnect_delay(),
+            Duration::from_secs(30)
+        );
+    }
+
     #[tokio::test]
     async fn config_prefixes_bot_to_token() {
         const WITHOUT: &str = "test";
@@ -189,6 +189,12 @@ impl Display for ReceiveMessageError {
                 f.write_str(event)
             }
             ReceiveMessageErrorType::Reconnect => f.write_str("failed to reconnect to the gateway"),
+            ReceiveMessageErrorType::ReconnectExhausted => {
+                f.write_str("gave up reconnecting to the gateway")
+            }
+            ReceiveMessageErrorType::SendingMessage { .. } => {
+                f.write_str("failed to send a command, even after reconnecting")
+            }
         }
     }
 }
@@ -219,6 +225,17 @@ pub enum ReceiveMessageErrorType {
     },
     /// Shard failed to reconnect to the gateway.
     Reconnect,
+    /// Shard failed to reconnect to the gateway and reached its
+    /// [maximum number of attempts], so it will not try again.
+    ///
+    /// [maximum number of attempts]: crate::ConfigBuilder::max_reconnect_attempts
+    ReconnectExhausted,
+    /// Command from the user channel could not be sent, neither over the
+    /// connection it was queued on nor once reconnected.
+    SendingMessage {
+        /// Serialized command.
+        command: String,
+    },
 }
 
 #[cfg(test)]
@@ -232,7 +249,7 @@ mod tests {
 
     #[test]
     fn receive_message_error_display() {
-        let messages: [(ReceiveMessageErrorType, &str); 3] = [
+        let messages: [(ReceiveMessageErrorType, &str); 5] = [
             (
                 ReceiveMessageErrorType::Compression,
                 "binary message could not be decompressed",
@@ -247,6 +264,16 @@ mod tests {
                 ReceiveMessageErrorType::Reconnect,
                 "failed to reconnect to the gateway",
             ),
+            (
+                ReceiveMessageErrorType::ReconnectExhausted,
+                "gave up reconnecting to the gateway",
+            ),
+            (
+                ReceiveMessageErrorType::SendingMessage {
+                    command: r#"{"op":3,"d":{}}"#.to_owned(),
+                },
+                "failed to send a command, even after reconnecting",
+            ),
         ];
 
         for (kind, message) in messages {
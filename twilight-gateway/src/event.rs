@@ -1,7 +1,10 @@
 //! Optimization for skipping deserialization of unwanted events.
 
 use bitflags::bitflags;
-use twilight_model::gateway::{event::EventType, OpCode};
+use twilight_model::gateway::{
+    event::{DispatchEvent, Event, EventType},
+    Intents, OpCode,
+};
 
 bitflags! {
     /// Important optimization for narrowing requested event types.
@@ -139,9 +142,9 @@ bitflags! {
         /// Multiple messages have been deleted in a channel.
         const MESSAGE_DELETE_BULK = 1 << 21;
         /// Message poll vote has been added.
-        const MESSAGE_POLL_VOTE_ADD = 1 << 28;
+        const MESSAGE_POLL_VOTE_ADD = 1 << 79;
         /// Message poll vote has been removed.
-        const MESSAGE_POLL_VOTE_REMOVE = 1 << 29;
+        const MESSAGE_POLL_VOTE_REMOVE = 1 << 80;
         /// Message in a channel has been updated.
         const MESSAGE_UPDATE = 1 << 22;
         /// User's presence details are updated.
@@ -332,6 +335,96 @@ bitflags! {
     }
 }
 
+/// Event types received when identifying with each intent.
+const INTENT_EVENT_TYPES: &[(Intents, EventTypeFlags)] = &[
+    (
+        Intents::AUTO_MODERATION_CONFIGURATION,
+        EventTypeFlags::AUTO_MODERATION_CONFIGURATION,
+    ),
+    (
+        Intents::AUTO_MODERATION_EXECUTION,
+        EventTypeFlags::AUTO_MODERATION_EXECUTION,
+    ),
+    (Intents::DIRECT_MESSAGES, EventTypeFlags::DIRECT_MESSAGES),
+    (Intents::DIRECT_MESSAGE_POLLS, EventTypeFlags::MESSAGE_POLLS),
+    (
+        Intents::DIRECT_MESSAGE_REACTIONS,
+        EventTypeFlags::DIRECT_MESSAGE_REACTIONS,
+    ),
+    (
+        Intents::DIRECT_MESSAGE_TYPING,
+        EventTypeFlags::DIRECT_MESSAGE_TYPING,
+    ),
+    (Intents::GUILDS, EventTypeFlags::GUILDS),
+    (
+        Intents::GUILD_EMOJIS_AND_STICKERS,
+        EventTypeFlags::GUILD_EMOJIS_AND_STICKERS,
+    ),
+    (
+        Intents::GUILD_INTEGRATIONS,
+        EventTypeFlags::GUILD_INTEGRATIONS,
+    ),
+    (Intents::GUILD_INVITES, EventTypeFlags::GUILD_INVITES),
+    (Intents::GUILD_MEMBERS, EventTypeFlags::GUILD_MEMBERS),
+    (Intents::GUILD_MESSAGES, EventTypeFlags::GUILD_MESSAGES),
+    (Intents::GUILD_MESSAGE_POLLS, EventTypeFlags::MESSAGE_POLLS),
+    (
+        Intents::GUILD_MESSAGE_REACTIONS,
+        EventTypeFlags::GUILD_MESSAGE_REACTIONS,
+    ),
+    (
+        Intents::GUILD_MESSAGE_TYPING,
+        EventTypeFlags::GUILD_MESSAGE_TYPING,
+    ),
+    (Intents::GUILD_MODERATION, EventTypeFlags::GUILD_MODERATION),
+    (Intents::GUILD_PRESENCES, EventTypeFlags::GUILD_PRESENCES),
+    (
+        Intents::GUILD_SCHEDULED_EVENTS,
+        EventTypeFlags::GUILD_SCHEDULED_EVENTS,
+    ),
+    (
+        Intents::GUILD_VOICE_STATES,
+        EventTypeFlags::GUILD_VOICE_STATES,
+    ),
+    (Intents::GUILD_WEBHOOKS, EventTypeFlags::GUILD_WEBHOOKS),
+];
+
+/// Event types that may be received when identifying with the intents.
+///
+/// Events that aren't gated behind an intent, such as
+/// [`READY`][EventTypeFlags::READY] and
+/// [`INTERACTION_CREATE`][EventTypeFlags::INTERACTION_CREATE], are always
+/// included.
+///
+/// This is useful for catching wanted event types that the configured intents
+/// never deliver:
+///
+/// ```
+/// use twilight_gateway::{EventTypeFlags, Intents};
+///
+/// let intents = Intents::GUILDS;
+/// let wanted_event_types = EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::ROLE_CREATE;
+///
+/// let missing = wanted_event_types.difference(intents.into());
+/// assert_eq!(missing, EventTypeFlags::MESSAGE_CREATE);
+/// ```
+impl From<Intents> for EventTypeFlags {
+    fn from(intents: Intents) -> Self {
+        let mut enabled = Self::empty();
+        let mut gated = Self::empty();
+
+        for &(intent, event_types) in INTENT_EVENT_TYPES {
+            gated |= event_types;
+
+            if intents.contains(intent) {
+                enabled |= event_types;
+            }
+        }
+
+        Self::all().difference(gated) | enabled
+    }
+}
+
 impl From<EventType> for EventTypeFlags {
     fn from(event_type: EventType) -> Self {
         match event_type {
@@ -412,6 +505,18 @@ impl From<EventType> for EventTypeFlags {
     }
 }
 
+impl From<&DispatchEvent> for EventTypeFlags {
+    fn from(event: &DispatchEvent) -> Self {
+        Self::from(event.kind())
+    }
+}
+
+impl From<&Event> for EventTypeFlags {
+    fn from(event: &Event) -> Self {
+        Self::from(event.kind())
+    }
+}
+
 impl TryFrom<(OpCode, Option<&str>)> for EventTypeFlags {
     type Error = ();
 
@@ -434,18 +539,165 @@ impl TryFrom<(OpCode, Option<&str>)> for EventTypeFlags {
 mod tests {
     use super::EventTypeFlags;
     use static_assertions::assert_impl_all;
-    use std::{fmt::Debug, hash::Hash};
-    use twilight_model::gateway::event::EventType;
+    use std::{collections::HashSet, fmt::Debug, hash::Hash};
+    use twilight_model::{
+        gateway::{
+            event::{DispatchEvent, Event, EventType},
+            payload::incoming::RoleDelete,
+            Intents,
+        },
+        id::Id,
+    };
 
     assert_impl_all!(
         EventTypeFlags: Copy,
         Clone,
         Debug,
         Eq,
+        From<&'static DispatchEvent>,
+        From<&'static Event>,
         From<EventType>,
+        From<Intents>,
         Hash,
         PartialEq,
         Send,
         Sync,
     );
+
+    #[test]
+    fn from_intents() {
+        let event_types = EventTypeFlags::from(Intents::empty());
+        assert!(event_types.contains(
+            EventTypeFlags::READY
+                | EventTypeFlags::INTERACTION_CREATE
+                | EventTypeFlags::MEMBER_CHUNK
+        ));
+        assert!(!event_types.intersects(EventTypeFlags::MESSAGE_CREATE));
+
+        let event_types = EventTypeFlags::from(Intents::GUILDS);
+        assert!(event_types.contains(EventTypeFlags::GUILDS));
+        assert!(!event_types.intersects(EventTypeFlags::MESSAGE_CREATE));
+
+        // Message events are delivered by either message intent.
+        for intents in [Intents::GUILD_MESSAGES, Intents::DIRECT_MESSAGES] {
+            assert!(EventTypeFlags::from(intents).contains(EventTypeFlags::MESSAGE_CREATE));
+        }
+
+        assert_eq!(EventTypeFlags::from(Intents::all()), EventTypeFlags::all());
+    }
+
+    /// Every event type except [`EventType::GatewayClose`], which has no flag.
+    const EVENT_TYPES: &[EventType] = &[
+        EventType::AutoModerationActionExecution,
+        EventType::AutoModerationRuleCreate,
+        EventType::AutoModerationRuleDelete,
+        EventType::AutoModerationRuleUpdate,
+        EventType::BanAdd,
+        EventType::BanRemove,
+        EventType::ChannelCreate,
+        EventType::ChannelDelete,
+        EventType::ChannelPinsUpdate,
+        EventType::ChannelUpdate,
+        EventType::CommandPermissionsUpdate,
+        EventType::EntitlementCreate,
+        EventType::EntitlementDelete,
+        EventType::EntitlementUpdate,
+        EventType::GatewayHeartbeat,
+        EventType::GatewayHeartbeatAck,
+        EventType::GatewayHello,
+        EventType::GatewayInvalidateSession,
+        EventType::GatewayReconnect,
+        EventType::GuildAuditLogEntryCreate,
+        EventType::GuildCreate,
+        EventType::GuildDelete,
+        EventType::GuildEmojisUpdate,
+        EventType::GuildIntegrationsUpdate,
+        EventType::GuildScheduledEventCreate,
+        EventType::GuildScheduledEventDelete,
+        EventType::GuildScheduledEventUpdate,
+        EventType::GuildScheduledEventUserAdd,
+        EventType::GuildScheduledEventUserRemove,
+        EventType::GuildStickersUpdate,
+        EventType::GuildUpdate,
+        EventType::IntegrationCreate,
+        EventType::IntegrationDelete,
+        EventType::IntegrationUpdate,
+        EventType::InteractionCreate,
+        EventType::InviteCreate,
+        EventType::InviteDelete,
+        EventType::MemberAdd,
+        EventType::MemberRemove,
+        EventType::MemberUpdate,
+        EventType::MemberChunk,
+        EventType::MessageCreate,
+        EventType::MessageDelete,
+        EventType::MessageDeleteBulk,
+        EventType::MessagePollVoteAdd,
+        EventType::MessagePollVoteRemove,
+        EventType::MessageUpdate,
+        EventType::PresenceUpdate,
+        EventType::ReactionAdd,
+        EventType::ReactionRemove,
+        EventType::ReactionRemoveAll,
+        EventType::ReactionRemoveEmoji,
+        EventType::Ready,
+        EventType::Resumed,
+        EventType::RoleCreate,
+        EventType::RoleDelete,
+        EventType::RoleUpdate,
+        EventType::StageInstanceCreate,
+        EventType::StageInstanceDelete,
+        EventType::StageInstanceUpdate,
+        EventType::ThreadCreate,
+        EventType::ThreadDelete,
+        EventType::ThreadListSync,
+        EventType::ThreadMembersUpdate,
+        EventType::ThreadMemberUpdate,
+        EventType::ThreadUpdate,
+        EventType::TypingStart,
+        EventType::UnavailableGuild,
+        EventType::UserUpdate,
+        EventType::VoiceServerUpdate,
+        EventType::VoiceStateUpdate,
+        EventType::WebhooksUpdate,
+    ];
+
+    #[test]
+    fn event_types_distinct() {
+        let mut seen = EventTypeFlags::empty();
+
+        for &event_type in EVENT_TYPES {
+            let flag = EventTypeFlags::from(event_type);
+            assert_eq!(flag.bits().count_ones(), 1, "{event_type:?}");
+            assert!(!seen.intersects(flag), "{event_type:?}");
+            seen |= flag;
+        }
+
+        assert_eq!(
+            EVENT_TYPES.iter().collect::<HashSet<_>>().len(),
+            EVENT_TYPES.len()
+        );
+        assert!(EventTypeFlags::from(EventType::GatewayClose).is_empty());
+    }
+
+    #[test]
+    fn from_event() {
+        let role_delete = RoleDelete {
+            guild_id: Id::new(1),
+            role_id: Id::new(2),
+        };
+
+        assert_eq!(
+            EventTypeFlags::from(&Event::RoleDelete(role_delete.clone())),
+            EventTypeFlags::ROLE_DELETE
+        );
+        assert_eq!(
+            EventTypeFlags::from(&DispatchEvent::RoleDelete(role_delete)),
+            EventTypeFlags::ROLE_DELETE
+        );
+        assert_eq!(
+            EventTypeFlags::from(&Event::GatewayHeartbeatAck),
+            EventTypeFlags::GATEWAY_HEARTBEAT_ACK
+        );
+    }
 }
@@ -82,3 +82,32 @@ pub fn parse(
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::{error::ReceiveMessageErrorType, EventTypeFlags};
+
+    #[test]
+    fn malformed() {
+        let error = parse("\u{0}garbage{".to_owned(), EventTypeFlags::all()).unwrap_err();
+
+        assert!(matches!(
+            error.kind(),
+            ReceiveMessageErrorType::Deserializing { event } if event == "\u{0}garbage{"
+        ));
+    }
+
+    /// Assert that dispatches unknown to the library, such as the
+    /// `READY_SUPPLEMENTAL` dispatch sent to user accounts, are skipped instead
+    /// of failing to deserialize.
+    #[test]
+    fn unknown_dispatch() {
+        let event =
+            r#"{"op":0,"s":2,"t":"READY_SUPPLEMENTAL","d":{"guilds":[],"merged_members":[]}}"#;
+
+        assert!(parse(event.to_owned(), EventTypeFlags::all())
+            .unwrap()
+            .is_none());
+    }
+}
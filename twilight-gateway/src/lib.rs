@@ -21,6 +21,8 @@ mod command;
 mod compression;
 mod config;
 mod event;
+#[cfg(feature = "http-proxy")]
+mod http_proxy;
 #[cfg(all(
     any(feature = "zlib-stock", feature = "zlib-simd"),
     not(feature = "zstd")
@@ -32,6 +34,7 @@ mod message;
 mod ratelimiter;
 mod session;
 mod shard;
+mod stats;
 mod stream;
 
 #[allow(deprecated)]
@@ -51,6 +54,7 @@ pub use self::{
     ratelimiter::CommandRatelimiter,
     session::Session,
     shard::{Shard, ShardState},
+    stats::Stats,
     stream::StreamExt,
 };
 pub use twilight_model::gateway::{CloseFrame, Intents, ShardId};
@@ -18,6 +18,8 @@ use std::mem;
 /// application's process needs to be restarted, then this session
 /// information—which can be (de)serialized via serde—can be stored, the
 /// application restarted, and then used again via [`ConfigBuilder::session`].
+/// The shard's [resume URL] should be stored alongside it and restored via
+/// [`ConfigBuilder::resume_url`].
 ///
 /// If the delay between disconnecting from the gateway and reconnecting isn't
 /// too long and Discord hasn't invalidated the session, then the session will
@@ -26,8 +28,10 @@ use std::mem;
 /// won't have missed any events. If the delay has been too long, then a new
 /// session will be initialized, resulting in those events being missed.
 ///
+/// [`ConfigBuilder::resume_url`]: crate::ConfigBuilder::resume_url
 /// [`ConfigBuilder::session`]: crate::ConfigBuilder::session
 /// [identifier]: Self::id
+/// [resume URL]: crate::Shard::resume_url
 /// [shard]: crate::Shard
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Session {
@@ -8,6 +8,8 @@
 
 #[cfg(feature = "zstd")]
 use crate::compression::Decompressor;
+#[cfg(feature = "http-proxy")]
+use crate::http_proxy;
 #[allow(deprecated)]
 #[cfg(all(
     any(feature = "zlib-stock", feature = "zlib-simd"),
@@ -22,6 +24,7 @@ use crate::{
     queue::{InMemoryQueue, Queue},
     ratelimiter::CommandRatelimiter,
     session::Session,
+    stats::Stats,
     Command, Config, Message, ShardId, API_VERSION,
 };
 use futures_core::Stream;
@@ -42,16 +45,19 @@ use tokio::{
     time::{self, Duration, Instant, Interval, MissedTickBehavior},
 };
 use tokio_websockets::{ClientBuilder, Error as WebsocketError, Limits, MaybeTlsStream};
-use twilight_model::gateway::{
-    event::GatewayEventDeserializer,
-    payload::{
-        incoming::Hello,
-        outgoing::{
-            identify::{IdentifyInfo, IdentifyProperties},
-            Heartbeat, Identify, Resume,
+use twilight_model::{
+    gateway::{
+        event::GatewayEventDeserializer,
+        payload::{
+            incoming::Hello,
+            outgoing::{
+                identify::{IdentifyInfo, IdentifyProperties},
+                Heartbeat, Identify, Resume,
+            },
         },
+        CloseCode, CloseFrame, Intents, OpCode,
     },
-    CloseCode, CloseFrame, Intents, OpCode,
+    id::{marker::GuildMarker, Id},
 };
 
 /// URL of the Discord gateway.
@@ -111,10 +117,12 @@ pub enum ShardState {
     ///
     /// Possible reasons may be due to [failed authentication],
     /// [invalid intents], or other reasons. Refer to the documentation for
-    /// [`CloseCode`] for possible reasons.
+    /// [`CloseCode`] for possible reasons. Shards also fatally close once they
+    /// reach their [maximum number of reconnection attempts].
     ///
     /// [failed authentication]: CloseCode::AuthenticationFailed
     /// [invalid intents]: CloseCode::InvalidIntents
+    /// [maximum number of reconnection attempts]: crate::ConfigBuilder::max_reconnect_attempts
     FatallyClosed,
     /// Shard is waiting to establish or resume a session.
     Identifying,
@@ -174,10 +182,22 @@ struct MinimalReady {
     session_id: String,
 }
 
+/// Command from the user channel.
+#[derive(Debug)]
+struct UserCommand {
+    /// Serialized command.
+    json: String,
+    /// Whether a previous connection failed to send the command.
+    is_retry: bool,
+}
+
 /// Pending outgoing message indicator.
 #[derive(Debug)]
 struct Pending {
-    /// The pending message, if not already sent.
+    /// Command from the user channel, only turned into a message when it's
+    /// sent to resend it if the connection is lost before then.
+    command: Option<UserCommand>,
+    /// The pending message, if not already sent or a command.
     gateway_event: Option<Message>,
     /// Whether the pending gateway event is a heartbeat.
     is_heartbeat: bool,
@@ -187,10 +207,39 @@ impl Pending {
     /// Constructor for a pending gateway event.
     const fn text(json: String, is_heartbeat: bool) -> Option<Self> {
         Some(Self {
+            command: None,
             gateway_event: Some(Message::Text(json)),
             is_heartbeat,
         })
     }
+
+    /// Constructor for a pending command from the user channel.
+    const fn command(command: UserCommand) -> Option<Self> {
+        Some(Self {
+            command: Some(command),
+            gateway_event: None,
+            is_heartbeat: false,
+        })
+    }
+
+    /// Whether the message has yet to be sent.
+    const fn is_unsent(&self) -> bool {
+        self.command.is_some() || self.gateway_event.is_some()
+    }
+
+    /// Whether sending the message counts towards the ratelimit.
+    fn is_ratelimited(&self) -> bool {
+        !self.is_heartbeat
+            && (self.command.is_some() || self.gateway_event.as_ref().is_some_and(Message::is_text))
+    }
+
+    /// Take the message to send, turning the command into one.
+    fn take_message(&mut self) -> Option<Message> {
+        match self.command.take() {
+            Some(command) => Some(Message::Text(command.json)),
+            None => self.gateway_event.take(),
+        }
+    }
 }
 
 /// Gateway API client responsible for up to 2500 guilds.
@@ -217,6 +266,17 @@ impl Pending {
 /// [Discord Docs/Sharding][docs:sharding] and [`ShardId`] documentation for
 /// more info.
 ///
+/// # Cancel safety
+///
+/// Polling a shard is cancel safe. Every piece of in-progress work---an
+/// ongoing connection attempt, a partially sent message, or a pending
+/// identify permit---is stored on the shard itself rather than in the future
+/// polling it. Dropping a future such as the one returned by [`next_event`]
+/// before it completes therefore never loses a message or leaves the session
+/// in an inconsistent state; the work continues on the next poll. This makes
+/// it safe to race the shard against other futures in `tokio::select!` or to
+/// wrap it in a timeout.
+///
 /// # Examples
 ///
 /// Create and start a shard and print new and deleted messages:
@@ -256,6 +316,7 @@ impl Pending {
 ///
 /// [docs:sharding]: https://discord.com/developers/docs/topics/gateway#sharding
 /// [gateway commands]: Shard::command
+/// [`next_event`]: crate::StreamExt::next_event
 /// [`poll_next`]: Shard::poll_next
 /// [`queue`]: crate::queue
 #[derive(Debug)]
@@ -299,6 +360,8 @@ pub struct Shard<Q = InMemoryQueue> {
     inflater: Inflater,
     /// Potentially pending outgoing message.
     pending: Option<Pending>,
+    /// When a heartbeat was last acknowledged, kept across connections.
+    last_ack: Option<Instant>,
     /// Recent heartbeat latency statistics.
     ///
     /// The latency is reset on receiving [`GatewayEvent::Hello`] as the host
@@ -318,6 +381,14 @@ pub struct Shard<Q = InMemoryQueue> {
     session: Option<Session>,
     /// Current state of the shard.
     state: ShardState,
+    /// Received event statistics, kept across connections.
+    stats: Stats,
+    /// Command from the user channel that the previous connection failed to
+    /// send.
+    unsent_command: Option<UserCommand>,
+    /// Command from the user channel that failed to send even when resent, to
+    /// be reported.
+    failed_command: Option<String>,
     /// Messages from the user to be relayed and sent over the Websocket
     /// connection.
     user_channel: MessageChannel,
@@ -357,6 +428,7 @@ impl<Q> Shard<Q> {
             ))]
             inflater: Inflater::new(),
             pending: None,
+            last_ack: None,
             latency: Latency::new(),
             ratelimiter: None,
             resume_url,
@@ -364,6 +436,9 @@ impl<Q> Shard<Q> {
             state: ShardState::Disconnected {
                 reconnect_attempts: 0,
             },
+            stats: Stats::new(),
+            unsent_command: None,
+            failed_command: None,
             user_channel: MessageChannel::new(),
         }
     }
@@ -378,6 +453,13 @@ impl<Q> Shard<Q> {
         self.id
     }
 
+    /// Whether the shard receives the events of a guild.
+    ///
+    /// Refer to [`ShardId::for_guild`] for how guilds are assigned to shards.
+    pub const fn owns_guild(&self, guild_id: Id<GuildMarker>) -> bool {
+        ShardId::for_guild(guild_id, self.id.total()).number() == self.id.number()
+    }
+
     /// Zlib decompressor statistics.
     ///
     /// Reset when reconnecting to the gateway.
@@ -404,6 +486,20 @@ impl<Q> Shard<Q> {
         &self.latency
     }
 
+    /// Time since a heartbeat was last acknowledged.
+    ///
+    /// Unlike [`latency`], this is kept when reconnecting to the gateway, so it
+    /// keeps growing while heartbeats go unacknowledged, even across resumes. A
+    /// value well above the heartbeat interval indicates the connection may be
+    /// failing.
+    ///
+    /// Returns [`None`] if no heartbeat has been acknowledged.
+    ///
+    /// [`latency`]: Self::latency
+    pub fn since_last_ack(&self) -> Option<Duration> {
+        self.last_ack.map(|last_ack| last_ack.elapsed())
+    }
+
     /// Statistics about the number of available commands and when the command
     /// ratelimiter will refresh.
     ///
@@ -431,9 +527,18 @@ impl<Q> Shard<Q> {
         self.session.as_ref()
     }
 
+    /// Shard statistics, including the number of received dispatch events and
+    /// when the last one was received.
+    ///
+    /// Kept when reconnecting to the gateway.
+    pub const fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
     /// Queue a command to be sent to the gateway.
     ///
-    /// Serializes the command and then calls [`send`].
+    /// Serializes the command and then calls [`send`], which also describes
+    /// when commands are resent.
     ///
     /// [`send`]: Self::send
     #[allow(clippy::missing_panics_doc)]
@@ -442,6 +547,12 @@ impl<Q> Shard<Q> {
     }
 
     /// Queue a JSON encoded gateway event to be sent to the gateway.
+    ///
+    /// Gateway events are sent at most once. One that could not be written
+    /// to the connection before it was lost is sent once reconnected, and if
+    /// that fails too, is returned in a
+    /// [`ReceiveMessageErrorType::SendingMessage`] error. One lost while
+    /// being written is not sent again, as the gateway may have received it.
     #[allow(clippy::missing_panics_doc)]
     pub fn send(&self, json: String) {
         self.user_channel
@@ -533,6 +644,22 @@ impl<Q> Shard<Q> {
                 reconnect_attempts: 0,
             },
         };
+        // Nothing pending may be sent anymore. Resend a command that wasn't
+        // sent once identified again, but only once.
+        if let Some(Pending {
+            command: Some(command),
+            ..
+        }) = self.pending.take()
+        {
+            if command.is_retry {
+                self.failed_command = Some(command.json);
+            } else {
+                self.unsent_command = Some(UserCommand {
+                    json: command.json,
+                    is_retry: true,
+                });
+            }
+        }
         if let CloseInitiator::Shard(frame) = initiator {
             // Not resuming, drop session and resume URL.
             // https://discord.com/developers/docs/topics/gateway#initiating-a-disconnect
@@ -541,6 +668,7 @@ impl<Q> Shard<Q> {
                 self.session = None;
             }
             self.pending = Some(Pending {
+                command: None,
                 gateway_event: Some(Message::Close(Some(frame))),
                 is_heartbeat: false,
             });
@@ -581,14 +709,14 @@ impl<Q: Queue> Shard<Q> {
             if let Some(pending) = self.pending.as_mut() {
                 ready!(Pin::new(self.connection.as_mut().unwrap()).poll_ready(cx))?;
 
-                if let Some(message) = &pending.gateway_event {
+                if pending.is_unsent() {
                     if let Some(ratelimiter) = self.ratelimiter.as_mut() {
-                        if message.is_text() && !pending.is_heartbeat {
+                        if pending.is_ratelimited() {
                             ready!(ratelimiter.poll_acquire(cx));
                         }
                     }
 
-                    let ws_message = pending.gateway_event.take().unwrap().into_websocket_msg();
+                    let ws_message = pending.take_message().unwrap().into_websocket_msg();
                     Pin::new(self.connection.as_mut().unwrap()).start_send(ws_message)?;
                 }
 
@@ -679,14 +807,22 @@ impl<Q: Queue> Shard<Q> {
             }
 
             if not_ratelimited && self.state.is_identified() {
-                if let Poll::Ready(command) = self.user_channel.command_rx.poll_recv(cx) {
+                // Resend the command the previous connection failed to send
+                // before any newer ones.
+                let command = match self.unsent_command.take() {
+                    Some(command) => Poll::Ready(Some(command)),
+                    None => self.user_channel.command_rx.poll_recv(cx).map(|json| {
+                        json.map(|json| UserCommand {
+                            json,
+                            is_retry: false,
+                        })
+                    }),
+                };
+                if let Poll::Ready(command) = command {
                     let command = command.expect("shard owns channel");
 
-                    tracing::debug!("sending command from user channel");
-                    self.pending = Some(Pending {
-                        gateway_event: Some(Message::Text(command)),
-                        is_heartbeat: false,
-                    });
+                    tracing::debug!(command.is_retry, "sending command from user channel");
+                    self.pending = Pending::command(command);
 
                     continue;
                 }
@@ -699,12 +835,15 @@ impl<Q: Queue> Shard<Q> {
     /// Updates the shard's internal state from a gateway event by recording
     /// and/or responding to certain Discord events.
     ///
+    /// Returns whether the event should be passed on. Dispatch events received
+    /// after a sequence gap are not, as resuming replays them.
+    ///
     /// # Errors
     ///
     /// Returns a [`ReceiveMessageErrorType::Deserializing`] error type if the
     /// gateway event isn't a recognized structure.
     #[allow(clippy::too_many_lines)]
-    fn process(&mut self, event: &str) -> Result<(), ReceiveMessageError> {
+    fn process(&mut self, event: &str) -> Result<bool, ReceiveMessageError> {
         let (raw_opcode, maybe_sequence, maybe_event_type) =
             GatewayEventDeserializer::from_json(event)
                 .ok_or_else(|| ReceiveMessageError {
@@ -735,20 +874,60 @@ impl<Q: Queue> Shard<Q> {
                 })?;
                 tracing::debug!(%event_type, %sequence, "received dispatch");
 
+                // A READY not on an active session starts a new session with
+                // its own sequence.
+                let is_new_session = event_type == "READY" && self.state != ShardState::Active;
+                if let Some(previous) = self
+                    .session
+                    .as_ref()
+                    .map(Session::sequence)
+                    .filter(|_| !is_new_session)
+                {
+                    if sequence > previous.saturating_add(1) {
+                        // Events were missed; resume from the last received
+                        // event to have them, and this one, replayed.
+                        tracing::info!(
+                            previous,
+                            sequence,
+                            "received dispatch after a sequence gap"
+                        );
+                        if !self.state.is_disconnected() {
+                            self.disconnect(CloseInitiator::Shard(CloseFrame::RESUME));
+                        }
+                        return Ok(false);
+                    }
+                }
+
+                // Only expected in response to identifying. Ignore one on an
+                // active session rather than replacing the session, though its
+                // sequence still continues the session's.
+                if event_type == "READY" && !is_new_session {
+                    tracing::warn!("ignoring ready received on an active session");
+                } else {
+                    self.stats.record_dispatch();
+                }
+
                 match event_type.as_ref() {
-                    "READY" => {
+                    "READY" if is_new_session => {
                         let event = Self::parse_event::<MinimalReady>(event)?;
 
                         self.resume_url = Some(event.data.resume_gateway_url);
                         self.session = Some(Session::new(sequence, event.data.session_id));
                         self.state = ShardState::Active;
                     }
-                    "RESUMED" => self.state = ShardState::Active,
+                    "RESUMED" => {
+                        self.state = ShardState::Active;
+                        self.stats.record_resume();
+                    }
                     _ => {}
                 }
 
+                // Events sent before the session was replaced may not rewind
+                // its sequence.
                 if let Some(session) = self.session.as_mut() {
-                    session.set_sequence(sequence);
+                    if sequence > session.sequence() {
+                        session.set_sequence(sequence);
+                    }
                 }
             }
             Some(OpCode::Heartbeat) => {
@@ -764,6 +943,7 @@ impl<Q: Queue> Shard<Q> {
                 if requested {
                     tracing::debug!("received heartbeat ack");
                     self.latency.record_received();
+                    self.last_ack = Some(Instant::now());
                 } else {
                     tracing::info!("received unrequested heartbeat ack");
                 }
@@ -819,7 +999,7 @@ impl<Q: Queue> Shard<Q> {
             _ => tracing::info!("received an unknown opcode: {raw_opcode}"),
         }
 
-        Ok(())
+        Ok(true)
     }
 }
 
@@ -828,16 +1008,21 @@ impl<Q: Queue + Unpin> Stream for Shard<Q> {
 
     #[allow(clippy::too_many_lines)]
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let message = loop {
+        let message = 'poll: loop {
+            if let Some(command) = self.failed_command.take() {
+                return Poll::Ready(Some(Err(ReceiveMessageError {
+                    kind: ReceiveMessageErrorType::SendingMessage { command },
+                    source: None,
+                })));
+            }
+
             match self.state {
                 ShardState::FatallyClosed => {
-                    _ = ready!(Pin::new(
-                        self.connection
-                            .as_mut()
-                            .expect("poll_next called after Poll::Ready(None)")
-                    )
-                    .poll_close(cx));
-                    self.connection = None;
+                    // There's no connection if reconnecting was given up.
+                    if let Some(connection) = self.connection.as_mut() {
+                        _ = ready!(Pin::new(connection).poll_close(cx));
+                        self.connection = None;
+                    }
                     return Poll::Ready(None);
                 }
                 ShardState::Disconnected { reconnect_attempts } if self.connection.is_none() => {
@@ -853,19 +1038,27 @@ impl<Q: Queue + Unpin> Stream for Shard<Q> {
 
                         tracing::debug!(url = base_url, "connecting to gateway");
 
+                        let delay =
+                            reconnect_delay(reconnect_attempts, self.config.max_reconnect_delay());
+                        #[cfg(feature = "http-proxy")]
+                        let http_proxy = self.config.http_proxy().map(Box::<str>::from);
                         let tls = self.config.tls.clone();
                         self.connection_future = Some(ConnectionFuture(Box::pin(async move {
-                            let secs = 2u8.saturating_pow(reconnect_attempts.into());
-                            time::sleep(Duration::from_secs(secs.into())).await;
+                            time::sleep(delay).await;
 
-                            Ok(ClientBuilder::new()
+                            let builder = ClientBuilder::new()
                                 .uri(&uri)
                                 .expect("URL should be valid")
                                 .limits(Limits::unlimited())
-                                .connector(&tls)
-                                .connect()
-                                .await?
-                                .0)
+                                .connector(&tls);
+
+                            #[cfg(feature = "http-proxy")]
+                            if let Some(http_proxy) = http_proxy {
+                                let stream = http_proxy::connect(&http_proxy, &uri, &tls).await?;
+                                return Ok(builder.connect_on(stream).await?.0);
+                            }
+
+                            Ok(builder.connect().await?.0)
                         })));
                     }
 
@@ -876,6 +1069,7 @@ impl<Q: Queue + Unpin> Stream for Shard<Q> {
                         Ok(connection) => {
                             self.connection = Some(connection);
                             self.state = ShardState::Identifying;
+                            self.stats.record_connection();
                             #[cfg(feature = "zstd")]
                             self.decompressor.reset();
                             #[allow(deprecated)]
@@ -886,13 +1080,23 @@ impl<Q: Queue + Unpin> Stream for Shard<Q> {
                             self.inflater.reset();
                         }
                         Err(source) => {
-                            self.resume_url = None;
-                            self.state = ShardState::Disconnected {
-                                reconnect_attempts: reconnect_attempts + 1,
+                            let reconnect_attempts = reconnect_attempts.saturating_add(1);
+                            let kind = if self
+                                .config
+                                .max_reconnect_attempts()
+                                .is_some_and(|max| reconnect_attempts >= max.get())
+                            {
+                                tracing::warn!(reconnect_attempts, "giving up reconnecting");
+                                self.state = ShardState::FatallyClosed;
+                                ReceiveMessageErrorType::ReconnectExhausted
+                            } else {
+                                self.state = ShardState::Disconnected { reconnect_attempts };
+                                ReceiveMessageErrorType::Reconnect
                             };
+                            self.resume_url = None;
 
                             return Poll::Ready(Some(Err(ReceiveMessageError {
-                                kind: ReceiveMessageErrorType::Reconnect,
+                                kind,
                                 source: Some(Box::new(source)),
                             })));
                         }
@@ -908,75 +1112,89 @@ impl<Q: Queue + Unpin> Stream for Shard<Q> {
                 return Poll::Ready(Some(Ok(Message::ABNORMAL_CLOSE)));
             }
 
-            match ready!(Pin::new(self.connection.as_mut().unwrap()).poll_next(cx)) {
-                Some(Ok(message)) => {
-                    #[cfg(feature = "zstd")]
-                    if message.is_binary() {
-                        match self.decompressor.decompress(message.as_payload()) {
-                            Ok(message) => break Message::Text(message),
-                            Err(source) => {
-                                self.disconnect(CloseInitiator::Shard(CloseFrame::RESUME));
-                                return Poll::Ready(Some(Err(
-                                    ReceiveMessageError::from_compression(source),
-                                )));
+            let message = 'received: {
+                match ready!(Pin::new(self.connection.as_mut().unwrap()).poll_next(cx)) {
+                    Some(Ok(message)) => {
+                        #[cfg(feature = "zstd")]
+                        if message.is_binary() {
+                            match self.decompressor.decompress(message.as_payload()) {
+                                Ok(message) => break 'received Message::Text(message),
+                                Err(source) => {
+                                    self.disconnect(CloseInitiator::Shard(CloseFrame::RESUME));
+                                    return Poll::Ready(Some(Err(
+                                        ReceiveMessageError::from_compression(source),
+                                    )));
+                                }
                             }
                         }
-                    }
-                    #[cfg(all(
-                        not(feature = "zstd"),
-                        any(feature = "zlib-stock", feature = "zlib-simd")
-                    ))]
-                    if message.is_binary() {
-                        match self.inflater.inflate(message.as_payload()) {
-                            Ok(Some(message)) => break Message::Text(message),
-                            Ok(None) => continue,
-                            Err(source) => {
-                                self.disconnect(CloseInitiator::Shard(CloseFrame::RESUME));
-                                return Poll::Ready(Some(Err(
-                                    ReceiveMessageError::from_compression(source),
-                                )));
+                        #[cfg(all(
+                            not(feature = "zstd"),
+                            any(feature = "zlib-stock", feature = "zlib-simd")
+                        ))]
+                        if message.is_binary() {
+                            match self.inflater.inflate(message.as_payload()) {
+                                Ok(Some(message)) => break 'received Message::Text(message),
+                                Ok(None) => continue 'poll,
+                                Err(source) => {
+                                    self.disconnect(CloseInitiator::Shard(CloseFrame::RESUME));
+                                    return Poll::Ready(Some(Err(
+                                        ReceiveMessageError::from_compression(source),
+                                    )));
+                                }
                             }
                         }
+                        if let Some(message) = Message::from_websocket_msg(&message) {
+                            break 'received message;
+                        }
                     }
-                    if let Some(message) = Message::from_websocket_msg(&message) {
-                        break message;
+                    Some(Err(_)) if self.state.is_disconnected() => {}
+                    Some(Err(_)) => {
+                        self.disconnect(CloseInitiator::Transport);
+                        return Poll::Ready(Some(Ok(Message::ABNORMAL_CLOSE)));
+                    }
+                    None => {
+                        _ = ready!(Pin::new(self.connection.as_mut().unwrap()).poll_close(cx));
+                        tracing::debug!("gateway WebSocket connection closed");
+                        // Unclean closure.
+                        if !self.state.is_disconnected() {
+                            self.disconnect(CloseInitiator::Transport);
+                        }
+                        self.connection = None;
                     }
                 }
-                Some(Err(_)) if self.state.is_disconnected() => {}
-                Some(Err(_)) => {
-                    self.disconnect(CloseInitiator::Transport);
-                    return Poll::Ready(Some(Ok(Message::ABNORMAL_CLOSE)));
-                }
-                None => {
-                    _ = ready!(Pin::new(self.connection.as_mut().unwrap()).poll_close(cx));
-                    tracing::debug!("gateway WebSocket connection closed");
-                    // Unclean closure.
+
+                continue 'poll;
+            };
+
+            match &message {
+                Message::Close(frame) => {
+                    // tokio-websockets automatically replies to the close message.
+                    tracing::debug!(?frame, "received WebSocket close message");
+                    // Don't run `disconnect` if we initiated the close.
                     if !self.state.is_disconnected() {
-                        self.disconnect(CloseInitiator::Transport);
+                        self.disconnect(CloseInitiator::Gateway(frame.as_ref().map(|f| f.code)));
                     }
-                    self.connection = None;
                 }
-            }
-        };
-
-        match &message {
-            Message::Close(frame) => {
-                // tokio-websockets automatically replies to the close message.
-                tracing::debug!(?frame, "received WebSocket close message");
-                // Don't run `disconnect` if we initiated the close.
-                if !self.state.is_disconnected() {
-                    self.disconnect(CloseInitiator::Gateway(frame.as_ref().map(|f| f.code)));
+                Message::Text(event) => {
+                    if !self.process(event)? {
+                        continue;
+                    }
                 }
             }
-            Message::Text(event) => {
-                self.process(event)?;
-            }
-        }
+
+            break message;
+        };
 
         Poll::Ready(Some(Ok(message)))
     }
 }
 
+/// Delay before a connection attempt, doubling with each consecutive failed
+/// attempt up to a maximum.
+fn reconnect_delay(reconnect_attempts: u8, max: Duration) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(reconnect_attempts.into())).min(max)
+}
+
 /// Default identify properties to use when the user hasn't customized it in
 /// [`Config::identify_properties`].
 ///
@@ -987,10 +1205,120 @@ fn default_identify_properties() -> IdentifyProperties {
 
 #[cfg(test)]
 mod tests {
-    use super::Shard;
+    use super::{reconnect_delay, CloseInitiator, Pending, Shard, UserCommand};
+    use crate::error::ReceiveMessageErrorType;
+    use futures_util::StreamExt;
     use static_assertions::{assert_impl_all, assert_not_impl_any};
-    use std::fmt::Debug;
+    use std::{fmt::Debug, time::Duration};
+    use tokio::time;
+    use twilight_model::{
+        gateway::{CloseFrame, Intents, ShardId},
+        id::Id,
+    };
 
     assert_impl_all!(Shard: Debug, Send);
     assert_not_impl_any!(Shard: Sync);
+
+    #[test]
+    fn reconnect_delay_backoff() {
+        const MAX: Duration = Duration::from_secs(128);
+
+        let delays = (0..=u8::MAX)
+            .map(|attempts| reconnect_delay(attempts, MAX).as_secs())
+            .collect::<Vec<_>>();
+
+        assert_eq!(delays[..9], [1, 2, 4, 8, 16, 32, 64, 128, 128]);
+        assert!(delays[9..].iter().all(|&delay| delay == 128));
+
+        assert_eq!(
+            reconnect_delay(3, Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn owns_guild() {
+        let shard = Shard::new(ShardId::new(2, 16), String::new(), Intents::empty());
+
+        assert!(shard.owns_guild(Id::new(81_384_788_765_712_384)));
+        assert!(!shard.owns_guild(Id::new(81_384_788_769_906_688)));
+    }
+
+    #[tokio::test]
+    async fn unsent_command_resent_once() {
+        let mut shard = Shard::new(ShardId::ONE, String::new(), Intents::empty());
+        shard.pending = Pending::command(UserCommand {
+            json: "command".to_owned(),
+            is_retry: false,
+        });
+
+        shard.disconnect(CloseInitiator::Transport);
+        assert!(shard.pending.is_none());
+        let command = shard.unsent_command.take().unwrap();
+        assert_eq!(command.json, "command");
+        assert!(command.is_retry);
+
+        // Sending started, but flushing failed.
+        shard.pending = Pending::command(UserCommand {
+            json: "command".to_owned(),
+            is_retry: false,
+        });
+        shard.pending.as_mut().unwrap().command = None;
+        shard.disconnect(CloseInitiator::Transport);
+        assert!(shard.unsent_command.is_none());
+
+        // Heartbeats are stale after reconnecting.
+        shard.pending = Pending::text("heartbeat".to_owned(), true);
+        shard.disconnect(CloseInitiator::Transport);
+        assert!(shard.pending.is_none());
+        assert!(shard.unsent_command.is_none());
+
+        // The retry also fails.
+        shard.pending = Pending::command(command);
+        shard.disconnect(CloseInitiator::Transport);
+        assert!(shard.unsent_command.is_none());
+
+        let error = shard.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ReceiveMessageErrorType::SendingMessage { command } if command == "command"
+        ));
+    }
+
+    #[tokio::test]
+    async fn unsent_command_kept_when_closing() {
+        let mut shard = Shard::new(ShardId::ONE, String::new(), Intents::empty());
+        shard.pending = Pending::command(UserCommand {
+            json: "command".to_owned(),
+            is_retry: false,
+        });
+
+        shard.disconnect(CloseInitiator::Shard(CloseFrame::RESUME));
+        assert!(shard.pending.as_ref().unwrap().command.is_none());
+        assert_eq!(shard.unsent_command.unwrap().json, "command");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn since_last_ack() {
+        const ACK: &str = r#"{"op":11,"d":null}"#;
+        const HELLO: &str = r#"{"op":10,"d":{"heartbeat_interval":41250}}"#;
+
+        let mut shard = Shard::new(ShardId::ONE, String::new(), Intents::empty());
+        assert!(shard.since_last_ack().is_none());
+
+        shard.latency.record_sent();
+        shard.process(ACK).unwrap();
+        assert_eq!(shard.since_last_ack(), Some(Duration::ZERO));
+
+        // Grows while no heartbeats are acknowledged.
+        shard.latency.record_sent();
+        time::advance(Duration::from_secs(5)).await;
+        assert_eq!(shard.since_last_ack(), Some(Duration::from_secs(5)));
+
+        // Kept across connections.
+        shard.process(HELLO).unwrap();
+        assert!(shard.latency().received().is_none());
+        time::advance(Duration::from_secs(5)).await;
+        assert_eq!(shard.since_last_ack(), Some(Duration::from_secs(10)));
+    }
 }
@@ -0,0 +1,121 @@
+//! Statistics about the events received by a shard, useful for monitoring.
+
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// [`Shard`]'s received event statistics.
+///
+/// Unlike [`Latency`], these are kept across connections. A connection whose
+/// heartbeats are acknowledged but which has stopped receiving dispatch
+/// events may be detected via [`since_last_dispatch`].
+///
+/// May be obtained via [`Shard::stats`].
+///
+/// [`Latency`]: crate::Latency
+/// [`Shard`]: crate::Shard
+/// [`Shard::stats`]: crate::Shard::stats
+/// [`since_last_dispatch`]: Self::since_last_dispatch
+#[derive(Clone, Debug)]
+pub struct Stats {
+    /// Number of successful connections to the gateway.
+    connections: u64,
+    /// Number of received dispatch events.
+    dispatches: u64,
+    /// When the last dispatch event was received.
+    last_dispatch: Option<Instant>,
+    /// Number of successfully resumed sessions.
+    resumes: u64,
+}
+
+impl Stats {
+    /// Create a new instance for tracking shard statistics.
+    pub(crate) const fn new() -> Self {
+        Self {
+            connections: 0,
+            dispatches: 0,
+            last_dispatch: None,
+            resumes: 0,
+        }
+    }
+
+    /// Number of received dispatch events, including those replayed when
+    /// resuming but excluding READY events received on an active session,
+    /// which are ignored.
+    pub const fn dispatches(&self) -> u64 {
+        self.dispatches
+    }
+
+    /// When the last dispatch event was received.
+    pub const fn last_dispatch(&self) -> Option<Instant> {
+        self.last_dispatch
+    }
+
+    /// Number of times the shard reconnected to the gateway.
+    pub const fn reconnects(&self) -> u64 {
+        self.connections.saturating_sub(1)
+    }
+
+    /// Number of times the shard resumed its session.
+    pub const fn resumes(&self) -> u64 {
+        self.resumes
+    }
+
+    /// Time since the last dispatch event was received.
+    ///
+    /// Returns [`None`] if no dispatch event has been received.
+    pub fn since_last_dispatch(&self) -> Option<Duration> {
+        self.last_dispatch
+            .map(|last_dispatch| last_dispatch.elapsed())
+    }
+
+    /// Record that a connection to the gateway was established.
+    pub(crate) fn record_connection(&mut self) {
+        self.connections += 1;
+    }
+
+    /// Record that a dispatch event was received.
+    pub(crate) fn record_dispatch(&mut self) {
+        self.dispatches += 1;
+        self.last_dispatch = Some(Instant::now());
+    }
+
+    /// Record that the session was resumed.
+    pub(crate) fn record_resume(&mut self) {
+        self.resumes += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stats;
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use tokio::time::Instant;
+
+    assert_impl_all!(Stats: Clone, Debug, Send, Sync);
+
+    #[test]
+    fn record() {
+        let mut stats = Stats::new();
+        assert_eq!(stats.dispatches(), 0);
+        assert!(stats.last_dispatch().is_none());
+        assert!(stats.since_last_dispatch().is_none());
+        assert_eq!(stats.reconnects(), 0);
+        assert_eq!(stats.resumes(), 0);
+
+        stats.record_connection();
+        assert_eq!(stats.reconnects(), 0);
+
+        let before = Instant::now();
+        stats.record_dispatch();
+        stats.record_dispatch();
+        assert_eq!(stats.dispatches(), 2);
+        assert!(stats.last_dispatch().unwrap() >= before);
+        assert!(stats.since_last_dispatch().unwrap() <= before.elapsed());
+
+        stats.record_connection();
+        stats.record_resume();
+        assert_eq!(stats.reconnects(), 1);
+        assert_eq!(stats.resumes(), 1);
+    }
+}
@@ -0,0 +1,1143 @@
+//! Drive shards through their lifecycle against a local mock gateway.
+//!
+//! Each test runs its own mock gateway, scripting the payloads it sends and
+//! recording the payloads it receives from the shard.
+//!
+//! The mock gateway is built from dev-dependencies only and speaks
+//! uncompressed JSON, which shards accept regardless of their compression
+//! feature, so these tests run under every feature combination.
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::{future::Future, net::SocketAddr, num::NonZeroU8, time::Duration};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    time,
+};
+use tokio_websockets::{CloseCode, Message as WebsocketMessage, ServerBuilder, WebSocketStream};
+use twilight_gateway::{
+    error::ReceiveMessageErrorType, queue::InMemoryQueue, CloseFrame, ConfigBuilder, Intents,
+    Message, Session, Shard, ShardId, ShardState,
+};
+use twilight_model::gateway::payload::outgoing::identify::IdentifyProperties;
+
+/// Heartbeat interval long enough for no heartbeat to be sent during the test.
+const HEARTBEAT_INTERVAL: u64 = 3_600_000;
+
+const SESSION_ID: &str = "session";
+
+/// Mock gateway connection.
+struct Connection(WebSocketStream<TcpStream>);
+
+impl Connection {
+    async fn accept(listener: &TcpListener) -> Self {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (_, stream) = ServerBuilder::new().accept(stream).await.unwrap();
+
+        Self(stream)
+    }
+
+    async fn send(&mut self, payload: Value) {
+        self.0
+            .send(WebsocketMessage::text(payload.to_string()))
+            .await
+            .unwrap();
+    }
+
+    async fn send_close(&mut self, code: u16) {
+        let code = CloseCode::try_from(code).unwrap();
+        self.0
+            .send(WebsocketMessage::close(Some(code), ""))
+            .await
+            .unwrap();
+    }
+
+    /// Receive the next text payload.
+    async fn receive(&mut self) -> Value {
+        let message = self.0.next().await.unwrap().unwrap();
+
+        serde_json::from_str(message.as_text().unwrap()).unwrap()
+    }
+
+    /// Receive the close code and reason, skipping over any other messages.
+    ///
+    /// Polls the connection to completion to flush the close reply.
+    async fn receive_close_frame(mut self) -> (u16, String) {
+        let mut close_frame = None;
+        while let Some(message) = self.0.next().await {
+            if let Some((code, reason)) = message.unwrap().as_close() {
+                close_frame = Some((code.into(), reason.to_owned()));
+            }
+        }
+
+        close_frame.expect("connection closed without a close frame")
+    }
+
+    /// Receive the close code, skipping over any other messages.
+    async fn receive_close(self) -> u16 {
+        self.receive_close_frame().await.0
+    }
+}
+
+fn dispatch(event_type: &str, sequence: u64, data: Value) -> Value {
+    json!({ "op": 0, "s": sequence, "t": event_type, "d": data })
+}
+
+fn hello() -> Value {
+    json!({ "op": 10, "d": { "heartbeat_interval": HEARTBEAT_INTERVAL } })
+}
+
+fn ready(url: &str) -> Value {
+    dispatch(
+        "READY",
+        1,
+        json!({ "resume_gateway_url": url, "session_id": SESSION_ID }),
+    )
+}
+
+async fn bind() -> (TcpListener, String) {
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+
+    (listener, url)
+}
+
+fn shard(url: String) -> Shard {
+    let config = ConfigBuilder::new("token".to_owned(), Intents::GUILD_MESSAGES)
+        .proxy_url(url)
+        .build();
+
+    Shard::with_config(ShardId::ONE, config)
+}
+
+/// Run the mock gateway, returning the payloads sent by the shard.
+async fn gateway(listener: TcpListener, url: String) -> Vec<Value> {
+    let mut received = Vec::new();
+
+    let mut connection = Connection::accept(&listener).await;
+    connection.send(hello()).await;
+    received.push(connection.receive().await);
+    connection.send(ready(&url)).await;
+    connection
+        .send(dispatch("MESSAGE_DELETE", 2, json!({ "id": "1" })))
+        .await;
+    connection.send(json!({ "op": 7, "d": null })).await;
+    assert_eq!(connection.receive_close().await, CloseFrame::RESUME.code);
+
+    let mut connection = Connection::accept(&listener).await;
+    connection.send(hello()).await;
+    received.push(connection.receive().await);
+    connection
+        .send(dispatch("MESSAGE_DELETE", 3, json!({ "id": "2" })))
+        .await;
+    connection.send(dispatch("RESUMED", 4, json!({}))).await;
+    connection.send_close(4004).await;
+    assert_eq!(connection.receive_close().await, 4004);
+
+    received
+}
+
+/// Receive the next message, asserting the shard hasn't stopped.
+async fn next(shard: &mut Shard) -> Message {
+    shard.next().await.unwrap().unwrap()
+}
+
+/// Receive the next text message, asserting it's a gateway event.
+async fn next_event(shard: &mut Shard) -> Value {
+    match next(shard).await {
+        Message::Text(json) => serde_json::from_str(&json).unwrap(),
+        Message::Close(frame) => panic!("expected gateway event, got close frame {frame:?}"),
+    }
+}
+
+async fn timeout<F: Future>(future: F) -> F::Output {
+    time::timeout(Duration::from_secs(10), future)
+        .await
+        .expect("lifecycle should complete")
+}
+
+#[tokio::test]
+async fn reconnect_resume_fatal_close() {
+    let (listener, url) = bind().await;
+    let gateway = tokio::spawn(gateway(listener, url.clone()));
+    let mut shard = shard(url.clone());
+
+    timeout(async {
+        // Identify a new session.
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert_eq!(shard.state(), ShardState::Active);
+        assert_eq!(shard.resume_url(), Some(url.as_str()));
+        assert_eq!(shard.session().unwrap().id(), SESSION_ID);
+
+        let event = next_event(&mut shard).await;
+        assert_eq!(
+            (&event["t"], &event["s"]),
+            (&json!("MESSAGE_DELETE"), &json!(2))
+        );
+
+        // Reconnect, keeping the session.
+        assert_eq!(next_event(&mut shard).await["op"], 7);
+        assert!(matches!(shard.state(), ShardState::Disconnected { .. }));
+        assert!(next(&mut shard).await.is_close());
+        assert_eq!(shard.session().unwrap().sequence(), 2);
+
+        // Resume the session.
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(shard.state(), ShardState::Resuming);
+        let event = next_event(&mut shard).await;
+        assert_eq!(
+            (&event["t"], &event["s"]),
+            (&json!("MESSAGE_DELETE"), &json!(3))
+        );
+        assert_eq!(next_event(&mut shard).await["t"], "RESUMED");
+        assert_eq!(shard.state(), ShardState::Active);
+
+        // Fatally closed.
+        match next(&mut shard).await {
+            Message::Close(Some(frame)) => assert_eq!(frame.code, 4004),
+            message => panic!("expected close frame, got {message:?}"),
+        }
+        assert_eq!(shard.state(), ShardState::FatallyClosed);
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let received = timeout(gateway).await.unwrap();
+    assert_eq!(received.len(), 2);
+
+    let identify = &received[0];
+    assert_eq!(identify["op"], 2);
+    assert_eq!(identify["d"]["token"], "Bot token");
+    assert_eq!(identify["d"]["intents"], Intents::GUILD_MESSAGES.bits());
+
+    let resume = &received[1];
+    assert_eq!(resume["op"], 6);
+    assert_eq!(resume["d"]["session_id"], SESSION_ID);
+    assert_eq!(resume["d"]["seq"], 2);
+}
+
+#[tokio::test]
+async fn close_reason() {
+    const REASON: &str = "shutting down for deploy";
+
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        assert_eq!(connection.receive().await["op"], 2);
+        connection.send(ready(&url)).await;
+
+        connection.receive_close_frame().await
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+
+        shard.close(CloseFrame::new(1000, REASON));
+        match next(&mut shard).await {
+            Message::Close(Some(frame)) => assert_eq!(frame.code, 1000),
+            message => panic!("expected close frame, got {message:?}"),
+        }
+    })
+    .await;
+
+    let (code, reason) = timeout(gateway).await.unwrap();
+    assert_eq!(code, 1000);
+    assert_eq!(reason, REASON);
+}
+
+#[tokio::test]
+async fn command_before_ready() {
+    const PRESENCE: &str =
+        r#"{"op":3,"d":{"activities":[],"afk":false,"since":null,"status":"online"}}"#;
+
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut received = Vec::new();
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        received.push(connection.receive().await);
+        connection.send(ready(&url)).await;
+        received.push(connection.receive().await);
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        received
+    });
+
+    // Queued before the shard has connected.
+    shard.send(PRESENCE.to_owned());
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let received = timeout(gateway).await.unwrap();
+    assert_eq!(received[0]["op"], 2);
+    assert_eq!(
+        received[1],
+        serde_json::from_str::<Value>(PRESENCE).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn command_before_reconnect() {
+    const PRESENCE: &str =
+        r#"{"op":3,"d":{"activities":[],"afk":false,"since":null,"status":"online"}}"#;
+
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut received = Vec::new();
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        received.push(connection.receive().await);
+        connection.send(ready(&url)).await;
+        connection.send(json!({ "op": 7, "d": null })).await;
+        received.push(connection.receive().await);
+        assert_eq!(connection.receive_close().await, CloseFrame::RESUME.code);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        received.push(connection.receive().await);
+        connection.send(dispatch("RESUMED", 2, json!({}))).await;
+        connection.send_close(4004).await;
+        // The command must not be sent again.
+        while let Some(message) = connection.0.next().await {
+            if let Some(payload) = message.unwrap().as_text() {
+                received.push(serde_json::from_str(payload).unwrap());
+            }
+        }
+
+        received
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+
+        // Queued while the reconnect request is waiting to be processed.
+        shard.send(PRESENCE.to_owned());
+
+        assert_eq!(next_event(&mut shard).await["op"], 7);
+        assert!(next(&mut shard).await.is_close());
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "RESUMED");
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let received = timeout(gateway).await.unwrap();
+    assert_eq!(received.len(), 3);
+    assert_eq!(received[0]["op"], 2);
+    assert_eq!(
+        received[1],
+        serde_json::from_str::<Value>(PRESENCE).unwrap()
+    );
+    assert_eq!(received[2]["op"], 6);
+}
+
+#[tokio::test]
+async fn first_heartbeat_null_sequence() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url);
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection
+            .send(json!({ "op": 10, "d": { "heartbeat_interval": 100 } }))
+            .await;
+
+        let heartbeat = loop {
+            let payload = connection.receive().await;
+            if payload["op"] == 1 {
+                break payload;
+            }
+        };
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        heartbeat
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let heartbeat = timeout(gateway).await.unwrap();
+    assert_eq!(heartbeat, json!({ "op": 1, "d": null }));
+}
+
+#[tokio::test]
+async fn duplicate_ready() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        connection.receive().await;
+        connection.send(ready(&url)).await;
+        connection
+            .send(dispatch(
+                "READY",
+                2,
+                json!({ "resume_gateway_url": "ws://127.0.0.1:1", "session_id": "duplicate" }),
+            ))
+            .await;
+        connection
+            .send(dispatch("MESSAGE_DELETE", 3, json!({ "id": "1" })))
+            .await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert_eq!(shard.state(), ShardState::Active);
+
+        let session = shard.session().unwrap();
+        assert_eq!(session.id(), SESSION_ID);
+        assert_eq!(session.sequence(), 2);
+        assert_ne!(shard.resume_url(), Some("ws://127.0.0.1:1"));
+        assert_eq!(shard.stats().dispatches(), 1);
+
+        // The session continues without resuming.
+        assert_eq!(next_event(&mut shard).await["t"], "MESSAGE_DELETE");
+        assert_eq!(shard.session().unwrap().sequence(), 3);
+        assert_eq!(shard.stats().dispatches(), 2);
+
+        match next(&mut shard).await {
+            Message::Close(Some(frame)) => assert_eq!(frame.code, 4004),
+            message => panic!("expected close frame, got {message:?}"),
+        }
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    timeout(gateway).await.unwrap();
+}
+
+#[tokio::test]
+async fn reidentify_keeps_config() {
+    let (listener, url) = bind().await;
+    let config = ConfigBuilder::new("token".to_owned(), Intents::GUILD_MESSAGES)
+        .identify_properties(IdentifyProperties::new("browser", "device", "os"))
+        .proxy_url(url.clone())
+        // Don't wait between identifies.
+        .queue(InMemoryQueue::new(0, 0, Duration::ZERO, 0))
+        .build();
+    let mut shard = Shard::with_config(ShardId::ONE, config);
+    let gateway = tokio::spawn(async move {
+        let mut received = Vec::new();
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        received.push(connection.receive().await);
+        connection.send(json!({ "op": 9, "d": false })).await;
+        assert_eq!(connection.receive_close().await, CloseFrame::NORMAL.code);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        received.push(connection.receive().await);
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        received
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["op"], 9);
+        assert!(next(&mut shard).await.is_close());
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let received = timeout(gateway).await.unwrap();
+    let properties = json!({ "browser": "browser", "device": "device", "os": "os" });
+    for identify in received {
+        assert_eq!(identify["op"], 2);
+        assert_eq!(identify["d"]["properties"], properties);
+        assert_eq!(identify["d"]["token"], "Bot token");
+    }
+}
+
+#[tokio::test]
+async fn malformed_payload() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        connection.receive().await;
+        connection.send(ready(&url)).await;
+        connection
+            .0
+            .send(WebsocketMessage::text("not json"))
+            .await
+            .unwrap();
+        connection
+            .send(dispatch("MESSAGE_DELETE", 2, json!({ "id": "1" })))
+            .await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+
+        let error = shard.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ReceiveMessageErrorType::Deserializing { event } if event == "not json"
+        ));
+        assert_eq!(shard.state(), ShardState::Active);
+
+        assert_eq!(next_event(&mut shard).await["s"], 2);
+        assert_eq!(shard.session().unwrap().sequence(), 2);
+
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    timeout(gateway).await.unwrap();
+}
+
+#[tokio::test]
+async fn reconnect_before_ready() {
+    let (listener, url) = bind().await;
+    let config = ConfigBuilder::new("token".to_owned(), Intents::GUILD_MESSAGES)
+        .proxy_url(url.clone())
+        // Don't wait between identifies.
+        .queue(InMemoryQueue::new(0, 0, Duration::ZERO, 0))
+        .build();
+    let mut shard = Shard::with_config(ShardId::ONE, config);
+    let gateway = tokio::spawn(async move {
+        let mut received = Vec::new();
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        received.push(connection.receive().await);
+        connection.send(json!({ "op": 7, "d": null })).await;
+        assert_eq!(connection.receive_close().await, CloseFrame::RESUME.code);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        received.push(connection.receive().await);
+        connection.send(ready(&url)).await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        received
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["op"], 7);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.session().is_none());
+
+        // There's no session to resume, so identify a new one.
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(shard.state(), ShardState::Identifying);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert_eq!(shard.state(), ShardState::Active);
+
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let received = timeout(gateway).await.unwrap();
+    assert_eq!(received.len(), 2);
+    for identify in received {
+        assert_eq!(identify["op"], 2);
+    }
+}
+
+#[tokio::test]
+async fn zombied_connection() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection
+            .send(json!({ "op": 10, "d": { "heartbeat_interval": 100 } }))
+            .await;
+        assert_eq!(connection.receive().await["op"], 2);
+        connection.send(ready(&url)).await;
+
+        // Never acknowledge the heartbeats, until the shard drops the
+        // connection.
+        let mut heartbeats = 0;
+        while let Some(Ok(message)) = connection.0.next().await {
+            let payload = serde_json::from_str::<Value>(message.as_text().unwrap()).unwrap();
+            assert_eq!(payload["op"], 1);
+            heartbeats += 1;
+        }
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        let resume = connection.receive().await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        (heartbeats, resume)
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+
+        // Zombied connections are closed abnormally and resumed.
+        match next(&mut shard).await {
+            Message::Close(Some(frame)) => assert_eq!(frame.code, 1006),
+            message => panic!("expected close frame, got {message:?}"),
+        }
+        assert!(matches!(shard.state(), ShardState::Disconnected { .. }));
+
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(shard.state(), ShardState::Resuming);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let (heartbeats, resume) = timeout(gateway).await.unwrap();
+    assert!(heartbeats > 0);
+    assert_eq!(resume["op"], 6);
+    assert_eq!(resume["d"]["session_id"], SESSION_ID);
+}
+
+#[tokio::test]
+async fn sequence_gap() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        connection.receive().await;
+        connection.send(ready(&url)).await;
+        connection
+            .send(dispatch("MESSAGE_DELETE", 2, json!({ "id": "1" })))
+            .await;
+        connection
+            .send(dispatch("MESSAGE_DELETE", 5, json!({ "id": "4" })))
+            .await;
+        assert_eq!(connection.receive_close().await, CloseFrame::RESUME.code);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        let resume = connection.receive().await;
+        for (sequence, id) in [(3, "2"), (4, "3"), (5, "4")] {
+            connection
+                .send(dispatch("MESSAGE_DELETE", sequence, json!({ "id": id })))
+                .await;
+        }
+        connection.send(dispatch("RESUMED", 6, Value::Null)).await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        resume
+    });
+
+    let sequences = timeout(async {
+        let mut sequences = Vec::new();
+        loop {
+            match shard.next().await {
+                Some(Ok(Message::Text(json))) => {
+                    let event = serde_json::from_str::<Value>(&json).unwrap();
+                    if event["op"] == 0 {
+                        sequences.push(event["s"].as_u64().unwrap());
+                    }
+                }
+                Some(Ok(Message::Close(_))) => {}
+                Some(Err(error)) => panic!("unexpected error: {error}"),
+                None => break sequences,
+            }
+        }
+    })
+    .await;
+
+    // Events 3 and 4 were missed, so the gap event 5 is dropped and all three
+    // are replayed when resuming.
+    assert_eq!(sequences, [1, 2, 3, 4, 5, 6]);
+
+    let resume = timeout(gateway).await.unwrap();
+    assert_eq!(resume["op"], 6);
+    assert_eq!(resume["d"]["seq"], 2);
+}
+
+#[tokio::test]
+async fn sequence_gap_after_resume() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut resumes = Vec::new();
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        connection.receive().await;
+        connection.send(ready(&url)).await;
+        connection.send(json!({ "op": 7, "d": null })).await;
+        assert_eq!(connection.receive_close().await, CloseFrame::RESUME.code);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        resumes.push(connection.receive().await);
+        connection.send(dispatch("RESUMED", 2, Value::Null)).await;
+        connection
+            .send(dispatch("MESSAGE_DELETE", 4, json!({ "id": "2" })))
+            .await;
+        assert_eq!(connection.receive_close().await, CloseFrame::RESUME.code);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        resumes.push(connection.receive().await);
+        for (sequence, id) in [(3, "1"), (4, "2")] {
+            connection
+                .send(dispatch("MESSAGE_DELETE", sequence, json!({ "id": id })))
+                .await;
+        }
+        connection.send(dispatch("RESUMED", 5, Value::Null)).await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        resumes
+    });
+
+    let sequences = timeout(async {
+        let mut sequences = Vec::new();
+        loop {
+            match shard.next().await {
+                Some(Ok(Message::Text(json))) => {
+                    let event = serde_json::from_str::<Value>(&json).unwrap();
+                    if event["op"] == 0 {
+                        sequences.push(event["s"].as_u64().unwrap());
+                    }
+                }
+                Some(Ok(Message::Close(_))) => {}
+                Some(Err(error)) => panic!("unexpected error: {error}"),
+                None => break sequences,
+            }
+        }
+    })
+    .await;
+
+    // The sequence continues from RESUMED, so event 3 was missed.
+    assert_eq!(sequences, [1, 2, 3, 4, 5]);
+
+    let resumes = timeout(gateway).await.unwrap();
+    assert_eq!(resumes[0]["d"]["seq"], 1);
+    assert_eq!(resumes[1]["d"]["seq"], 2);
+}
+
+#[tokio::test]
+async fn heartbeat_request() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        connection.receive().await;
+        connection.send(ready(&url)).await;
+        connection
+            .send(dispatch("MESSAGE_DELETE", 2, json!({ "id": "1" })))
+            .await;
+        // The requested sequence is ahead of the shard's.
+        connection.send(json!({ "op": 1, "d": 5 })).await;
+        let heartbeat = connection.receive().await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        heartbeat
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert_eq!(next_event(&mut shard).await["s"], 2);
+
+        // Reply with a heartbeat, without reconnecting.
+        assert_eq!(next_event(&mut shard).await["op"], 1);
+        assert_eq!(shard.state(), ShardState::Active);
+        assert_eq!(shard.session().unwrap().sequence(), 2);
+
+        match next(&mut shard).await {
+            Message::Close(Some(frame)) => assert_eq!(frame.code, 4004),
+            message => panic!("expected close frame, got {message:?}"),
+        }
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let heartbeat = timeout(gateway).await.unwrap();
+    assert_eq!(heartbeat, json!({ "op": 1, "d": 2 }));
+}
+
+#[cfg(feature = "http-proxy")]
+#[tokio::test]
+async fn http_proxy() {
+    use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+
+    let (listener, url) = bind().await;
+    let proxy_listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .unwrap();
+    let proxy_url = format!("http://{}", proxy_listener.local_addr().unwrap());
+
+    let config = ConfigBuilder::new("token".to_owned(), Intents::GUILD_MESSAGES)
+        .http_proxy(proxy_url)
+        .proxy_url(url.clone())
+        .build();
+    let mut shard = Shard::with_config(ShardId::ONE, config);
+
+    // Tunnel a single connection to the requested authority.
+    let proxy = tokio::spawn(async move {
+        let (mut client, _) = proxy_listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        while !request.ends_with(b"\r\n\r\n") {
+            request.push(client.read_u8().await.unwrap());
+        }
+        let request = String::from_utf8(request).unwrap();
+        let authority = request
+            .strip_prefix("CONNECT ")
+            .and_then(|request| request.split(' ').next())
+            .unwrap()
+            .to_owned();
+
+        let mut server = TcpStream::connect(&authority).await.unwrap();
+        client
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .await
+            .unwrap();
+        _ = io::copy_bidirectional(&mut client, &mut server).await;
+
+        authority
+    });
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        let identify = connection.receive().await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        identify
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    assert_eq!(timeout(gateway).await.unwrap()["op"], 2);
+    assert_eq!(
+        format!("ws://{}", timeout(proxy).await.unwrap()),
+        url.as_str()
+    );
+}
+
+#[tokio::test]
+async fn commands_survive_connection_loss() {
+    const COMMANDS: u64 = 200;
+
+    /// Receive commands until none arrive for a while.
+    async fn receive_commands(connection: &mut Connection, commands: &mut Vec<u64>) {
+        while let Ok(payload) =
+            time::timeout(Duration::from_millis(500), connection.receive()).await
+        {
+            commands.push(payload["d"].as_u64().unwrap());
+        }
+    }
+
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut commands = Vec::new();
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        connection.receive().await;
+        connection.send(ready(&url)).await;
+        // The ratelimiter holds back the remaining commands.
+        receive_commands(&mut connection, &mut commands).await;
+        drop(connection);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        let resume = connection.receive().await;
+        connection.send(dispatch("RESUMED", 2, json!({}))).await;
+        receive_commands(&mut connection, &mut commands).await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        (resume, commands)
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        for n in 0..COMMANDS {
+            shard.send(json!({ "op": 3, "d": n }).to_string());
+        }
+
+        // Reconnect after the connection is lost.
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "RESUMED");
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let (resume, commands) = timeout(gateway).await.unwrap();
+    assert_eq!(resume["op"], 6);
+    assert_eq!(commands, (0..COMMANDS).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn reconnect_attempts_exhausted() {
+    let (listener, url) = bind().await;
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let config = ConfigBuilder::new("token".to_owned(), Intents::GUILD_MESSAGES)
+        .proxy_url(url.clone())
+        .max_reconnect_attempts(NonZeroU8::new(2).unwrap())
+        .max_reconnect_delay(Duration::ZERO)
+        .build();
+    let mut shard = Shard::with_config(ShardId::ONE, config);
+
+    timeout(async {
+        let error = shard.next().await.unwrap().unwrap_err();
+        assert!(matches!(error.kind(), ReceiveMessageErrorType::Reconnect));
+        assert_eq!(
+            shard.state(),
+            ShardState::Disconnected {
+                reconnect_attempts: 1
+            }
+        );
+
+        // Connecting succeeds, resetting the attempt count.
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let gateway = tokio::spawn(async move {
+            let mut connection = Connection::accept(&listener).await;
+            drop(listener);
+            connection.send(hello()).await;
+            assert_eq!(connection.receive().await["op"], 2);
+            connection.send(ready(&url)).await;
+            connection.send_close(4000).await;
+            connection.receive_close().await;
+        });
+
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert!(next(&mut shard).await.is_close());
+
+        let error = shard.next().await.unwrap().unwrap_err();
+        assert!(matches!(error.kind(), ReceiveMessageErrorType::Reconnect));
+        let error = shard.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ReceiveMessageErrorType::ReconnectExhausted
+        ));
+        assert_eq!(shard.state(), ShardState::FatallyClosed);
+        assert!(shard.next().await.is_none());
+
+        gateway.await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn restore_session() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        assert_eq!(connection.receive().await["op"], 2);
+        connection.send(ready(&url)).await;
+        connection.send(dispatch("RESUMED", 2, Value::Null)).await;
+
+        // The restarted process resumes, which is rejected.
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        let resume = connection.receive().await;
+        connection.send(json!({ "op": 9, "d": false })).await;
+        assert_eq!(connection.receive_close().await, CloseFrame::NORMAL.code);
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        let identify = connection.receive().await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        (resume, identify)
+    });
+
+    let (session, resume_url) = timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        assert_eq!(next_event(&mut shard).await["t"], "RESUMED");
+
+        let session = serde_json::to_string(shard.session().unwrap()).unwrap();
+        (session, shard.resume_url().unwrap().to_owned())
+    })
+    .await;
+    drop(shard);
+
+    let config = ConfigBuilder::new("token".to_owned(), Intents::GUILD_MESSAGES)
+        .proxy_url(resume_url.clone())
+        // Don't wait between identifies.
+        .queue(InMemoryQueue::new(0, 0, Duration::ZERO, 0))
+        .resume_url(resume_url)
+        .session(serde_json::from_str(&session).unwrap())
+        .build();
+    let mut shard = Shard::with_config(ShardId::ONE, config);
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(shard.state(), ShardState::Resuming);
+        assert_eq!(next_event(&mut shard).await["op"], 9);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.session().is_none());
+
+        // Fall back to identifying a new session.
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(shard.state(), ShardState::Identifying);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let (resume, identify) = timeout(gateway).await.unwrap();
+    assert_eq!(resume["op"], 6);
+    assert_eq!(resume["d"]["session_id"], SESSION_ID);
+    assert_eq!(resume["d"]["seq"], 2);
+    assert_eq!(identify["op"], 2);
+}
+
+#[tokio::test]
+async fn restore_session_resumed() {
+    let (listener, url) = bind().await;
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        let resume = connection.receive().await;
+        connection
+            .send(dispatch("MESSAGE_DELETE", 6, json!({ "id": "1" })))
+            .await;
+        connection.send(dispatch("RESUMED", 7, Value::Null)).await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+
+        resume
+    });
+
+    let config = ConfigBuilder::new("token".to_owned(), Intents::GUILD_MESSAGES)
+        // Nothing listens here, so connecting only succeeds on the resume URL.
+        .proxy_url("ws://127.0.0.1:1".to_owned())
+        .resume_url(url)
+        .session(Session::new(5, SESSION_ID.to_owned()))
+        .build();
+    let mut shard = Shard::with_config(ShardId::ONE, config);
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(shard.state(), ShardState::Resuming);
+        let event = next_event(&mut shard).await;
+        assert_eq!(
+            (&event["t"], &event["s"]),
+            (&json!("MESSAGE_DELETE"), &json!(6))
+        );
+        assert_eq!(next_event(&mut shard).await["t"], "RESUMED");
+        assert_eq!(shard.state(), ShardState::Active);
+        assert_eq!(shard.session().unwrap().sequence(), 7);
+
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    let resume = timeout(gateway).await.unwrap();
+    assert_eq!(resume["op"], 6);
+    assert_eq!(resume["d"]["token"], "Bot token");
+    assert_eq!(resume["d"]["session_id"], SESSION_ID);
+    assert_eq!(resume["d"]["seq"], 5);
+}
+
+#[tokio::test]
+async fn stats() {
+    let (listener, url) = bind().await;
+    let mut shard = shard(url.clone());
+    let gateway = tokio::spawn(async move {
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        assert_eq!(connection.receive().await["op"], 2);
+        connection.send(ready(&url)).await;
+        connection
+            .send(dispatch(
+                "GUILD_CREATE",
+                2,
+                json!({ "id": "1", "unavailable": true }),
+            ))
+            .await;
+        connection.send_close(4000).await;
+        connection.receive_close().await;
+
+        let mut connection = Connection::accept(&listener).await;
+        connection.send(hello()).await;
+        assert_eq!(connection.receive().await["op"], 6);
+        connection.send(dispatch("RESUMED", 3, Value::Null)).await;
+        connection.send_close(4004).await;
+        connection.receive_close().await;
+    });
+
+    timeout(async {
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(shard.stats().dispatches(), 0);
+        assert!(shard.stats().last_dispatch().is_none());
+
+        assert_eq!(next_event(&mut shard).await["t"], "READY");
+        let last_dispatch = shard.stats().last_dispatch().unwrap();
+        assert_eq!(next_event(&mut shard).await["t"], "GUILD_CREATE");
+        assert_eq!(shard.stats().dispatches(), 2);
+        assert!(shard.stats().last_dispatch().unwrap() >= last_dispatch);
+        assert_eq!(shard.stats().reconnects(), 0);
+        assert!(next(&mut shard).await.is_close());
+
+        assert_eq!(next_event(&mut shard).await["op"], 10);
+        assert_eq!(next_event(&mut shard).await["t"], "RESUMED");
+        assert_eq!(shard.stats().dispatches(), 3);
+        assert_eq!(shard.stats().reconnects(), 1);
+        assert_eq!(shard.stats().resumes(), 1);
+        assert!(next(&mut shard).await.is_close());
+        assert!(shard.next().await.is_none());
+    })
+    .await;
+
+    timeout(gateway).await.unwrap();
+}
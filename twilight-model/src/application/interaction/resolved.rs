@@ -106,7 +106,7 @@ mod tests {
             },
             Attachment, ChannelType, Message,
         },
-        guild::{MemberFlags, PartialMember, Permissions, Role, RoleFlags},
+        guild::{MemberFlags, PartialMember, Permissions, Role, RoleColors, RoleFlags},
         id::Id,
         test::image_hash,
         user::{PremiumType, User, UserFlags},
@@ -249,6 +249,7 @@ mod tests {
                 Id::new(400),
                 Role {
                     color: 0,
+                    colors: RoleColors::solid(0),
                     hoist: true,
                     icon: None,
                     id: Id::new(400),
@@ -502,10 +503,18 @@ mod tests {
                 Token::Str("400"),
                 Token::Struct {
                     name: "Role",
-                    len: 9,
+                    len: 10,
                 },
                 Token::Str("color"),
                 Token::U32(0),
+                Token::Str("colors"),
+                Token::Struct {
+                    name: "RoleColors",
+                    len: 1,
+                },
+                Token::Str("primary_color"),
+                Token::U32(0),
+                Token::StructEnd,
                 Token::Str("hoist"),
                 Token::Bool(true),
                 Token::Str("id"),
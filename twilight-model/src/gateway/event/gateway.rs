@@ -478,6 +478,11 @@ mod tests {
     "roles": [
       {{
         "color": 0,
+        "colors": {{
+          "primary_color": 0,
+          "secondary_color": null,
+          "tertiary_color": null
+        }},
         "hoist": false,
         "id": "13312",
         "managed": false,
@@ -554,6 +559,11 @@ mod tests {
     "roles": [
       {{
         "color": 0,
+        "colors": {{
+          "primary_color": 0,
+          "secondary_color": null,
+          "tertiary_color": null
+        }},
         "hoist": false,
         "id": "47",
         "managed": false,
@@ -731,6 +741,31 @@ mod tests {
         assert!(matches!(event, GatewayEvent::HeartbeatAck));
     }
 
+    #[test]
+    fn round_trip() {
+        const INPUTS: &[&str] = &[
+            r#"{"t":"GUILD_ROLE_DELETE","s":7,"op":0,"d":{"guild_id":"1","role_id":"2"}}"#,
+            r#"{"t":"MESSAGE_DELETE","s":8,"op":0,"d":{"channel_id":"1","guild_id":"2","id":"3"}}"#,
+            r#"{"t":"CHANNEL_PINS_UPDATE","s":9,"op":0,"d":{"channel_id":"1","guild_id":"2","last_pin_timestamp":null}}"#,
+            r#"{"t":null,"s":null,"op":10,"d":{"heartbeat_interval":41250}}"#,
+            r#"{"t":null,"s":null,"op":9,"d":true}"#,
+            r#"{"t":null,"s":null,"op":7,"d":null}"#,
+        ];
+
+        for input in INPUTS {
+            let deserializer = GatewayEventDeserializer::from_json(input).unwrap();
+            let event = deserializer
+                .deserialize(&mut Deserializer::from_str(input))
+                .unwrap();
+
+            assert_eq!(
+                serde_json::to_value(&event).unwrap(),
+                serde_json::from_str::<serde_json::Value>(input).unwrap(),
+                "{input}"
+            );
+        }
+    }
+
     #[test]
     fn serialize_dispatch() {
         let role_delete = RoleDelete {
@@ -1,3 +1,4 @@
+use crate::id::{marker::GuildMarker, Id};
 use serde::{Deserialize, Serialize};
 use std::{
     error::Error,
@@ -72,6 +73,8 @@ pub enum ShardIdParseErrorType {
 ///
 /// > `number = (guild_id >> 22) % total`.
 ///
+/// This is implemented by [`ShardId::for_guild`].
+///
 /// `total` is in other words unrelated to the total number of shards and is
 /// only used to specify the share of events a shard will receive. The formula
 /// is independently calculated for all shards, which means that events may be
@@ -150,6 +153,34 @@ impl ShardId {
         }
     }
 
+    /// Create the identifier of the shard that receives a guild's events out of
+    /// a total number of shards.
+    ///
+    /// Calculated as `(guild_id >> 22) % total`, see the [type-level
+    /// documentation] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_model::{gateway::ShardId, id::Id};
+    ///
+    /// let id = ShardId::for_guild(Id::new(81_384_788_765_712_384), 16);
+    /// assert_eq!(id, ShardId::new(2, 16));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total number of shards is 0.
+    ///
+    /// [type-level documentation]: Self#advanced-use
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn for_guild(guild_id: Id<GuildMarker>, total: u32) -> Self {
+        assert!(total != 0, "total must be at least 1");
+
+        // The remainder is less than `total`, so it fits in a `u32`.
+        Self::new(((guild_id.get() >> 22) % total as u64) as u32, total)
+    }
+
     /// Identifying number of the shard, 0-indexed.
     pub const fn number(self) -> u32 {
         self.number
@@ -191,6 +222,7 @@ impl From<ShardId> for [u32; 2] {
 #[cfg(test)]
 mod tests {
     use super::ShardId;
+    use crate::id::Id;
     use serde::{de::DeserializeOwned, Serialize};
     use serde_test::Token;
     use static_assertions::{assert_impl_all, const_assert_eq};
@@ -227,6 +259,27 @@ mod tests {
         assert!(id.total() == 4);
     }
 
+    #[test]
+    fn for_guild() {
+        let guild_id = Id::new(81_384_788_765_712_384);
+        assert_eq!(ShardId::for_guild(guild_id, 1), ShardId::ONE);
+        assert_eq!(ShardId::for_guild(guild_id, 2), ShardId::new(0, 2));
+        assert_eq!(ShardId::for_guild(guild_id, 16), ShardId::new(2, 16));
+        assert_eq!(ShardId::for_guild(guild_id, 100), ShardId::new(98, 100));
+
+        let guild_id = Id::new(81_384_788_769_906_688);
+        assert_eq!(ShardId::for_guild(guild_id, 1), ShardId::ONE);
+        assert_eq!(ShardId::for_guild(guild_id, 2), ShardId::new(1, 2));
+        assert_eq!(ShardId::for_guild(guild_id, 16), ShardId::new(3, 16));
+        assert_eq!(ShardId::for_guild(guild_id, 100), ShardId::new(99, 100));
+    }
+
+    #[should_panic(expected = "total must be at least 1")]
+    #[test]
+    fn for_guild_zero_total() {
+        ShardId::for_guild(Id::new(1), 0);
+    }
+
     #[test]
     fn serde() {
         let value = ShardId::new(0, 1);
@@ -14,7 +14,7 @@ pub struct RoleUpdate {
 mod tests {
     use super::{Role, RoleUpdate};
     use crate::{
-        guild::{Permissions, RoleFlags},
+        guild::{Permissions, RoleColors, RoleFlags},
         id::Id,
     };
     use serde_test::Token;
@@ -25,6 +25,7 @@ mod tests {
             guild_id: Id::new(1),
             role: Role {
                 color: 0,
+                colors: RoleColors::solid(0),
                 hoist: true,
                 icon: None,
                 id: Id::new(1),
@@ -52,10 +53,18 @@ mod tests {
                 Token::Str("role"),
                 Token::Struct {
                     name: "Role",
-                    len: 9,
+                    len: 10,
                 },
                 Token::Str("color"),
                 Token::U32(0),
+                Token::Str("colors"),
+                Token::Struct {
+                    name: "RoleColors",
+                    len: 1,
+                },
+                Token::Str("primary_color"),
+                Token::U32(0),
+                Token::StructEnd,
                 Token::Str("hoist"),
                 Token::Bool(true),
                 Token::Str("id"),
@@ -15,3 +15,48 @@ impl Heartbeat {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Heartbeat;
+    use serde_test::Token;
+
+    /// Assert that heartbeats sent before any dispatch was received have a
+    /// null sequence rather than `0`.
+    #[test]
+    fn no_sequence() {
+        serde_test::assert_tokens(
+            &Heartbeat::new(None),
+            &[
+                Token::Struct {
+                    name: "Heartbeat",
+                    len: 2,
+                },
+                Token::Str("d"),
+                Token::None,
+                Token::Str("op"),
+                Token::U8(1),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn sequence() {
+        serde_test::assert_tokens(
+            &Heartbeat::new(Some(0)),
+            &[
+                Token::Struct {
+                    name: "Heartbeat",
+                    len: 2,
+                },
+                Token::Str("d"),
+                Token::Some,
+                Token::U64(0),
+                Token::Str("op"),
+                Token::U8(1),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -36,6 +36,7 @@ mod premium_tier;
 mod preview;
 mod prune;
 mod role;
+mod role_colors;
 mod role_flags;
 mod role_position;
 mod role_tags;
@@ -46,6 +47,7 @@ mod verification_level;
 
 pub use self::nsfw_level::NSFWLevel;
 pub use self::permissions::Permissions;
+pub use self::role_colors::{RoleColors, RoleColorsParseError, RoleColorsParseErrorType};
 pub use self::{
     afk_timeout::AfkTimeout, ban::Ban,
     default_message_notification_level::DefaultMessageNotificationLevel, emoji::Emoji,
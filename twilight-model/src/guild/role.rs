@@ -1,4 +1,4 @@
-use super::{RoleFlags, RoleTags};
+use super::{RoleColors, RoleFlags, RoleTags};
 use crate::{
     guild::Permissions,
     id::{marker::RoleMarker, Id},
@@ -10,6 +10,11 @@ use std::cmp::Ordering;
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct Role {
     pub color: u32,
+    /// Colors of the role.
+    ///
+    /// Defaults to no color if absent, such as in older payloads.
+    #[serde(default)]
+    pub colors: RoleColors,
     pub hoist: bool,
     /// Icon image hash.
     ///
@@ -57,12 +62,13 @@ impl Ord for Role {
     /// Compare the position of two roles:
     ///
     /// ```
-    /// # use twilight_model::{guild::{Permissions, Role, RoleFlags}, id::Id};
+    /// # use twilight_model::{guild::{Permissions, Role, RoleColors, RoleFlags}, id::Id};
     /// # use std::cmp::Ordering;
     /// let role_a = Role {
     ///     id: Id::new(123),
     ///     position: 12,
     /// #   color: 0,
+    /// #   colors: RoleColors::solid(0),
     /// #   hoist: true,
     /// #   icon: None,
     /// #   managed: false,
@@ -78,6 +84,7 @@ impl Ord for Role {
     ///     id: Id::new(456),
     ///     position: 13,
     /// #   color: 0,
+    /// #   colors: RoleColors::solid(0),
     /// #   hoist: true,
     /// #   icon: None,
     /// #   managed: false,
@@ -98,12 +105,13 @@ impl Ord for Role {
     /// Compare the position of two roles with the same position:
     ///
     /// ```
-    /// # use twilight_model::{guild::{Permissions, Role, RoleFlags}, id::Id};
+    /// # use twilight_model::{guild::{Permissions, Role, RoleColors, RoleFlags}, id::Id};
     /// # use std::cmp::Ordering;
     /// let role_a = Role {
     ///     id: Id::new(123),
     ///     position: 12,
     /// #   color: 0,
+    /// #   colors: RoleColors::solid(0),
     /// #   hoist: true,
     /// #   icon: None,
     /// #   managed: false,
@@ -118,6 +126,7 @@ impl Ord for Role {
     ///     id: Id::new(456),
     ///     position: 12,
     /// #   color: 0,
+    /// #   colors: RoleColors::solid(0),
     /// #   hoist: true,
     /// #   icon: None,
     /// #   managed: false,
@@ -149,7 +158,10 @@ impl PartialOrd for Role {
 #[cfg(test)]
 mod tests {
     use super::{Permissions, Role};
-    use crate::{guild::RoleFlags, id::Id};
+    use crate::{
+        guild::{RoleColors, RoleFlags},
+        id::Id,
+    };
     use serde::{Deserialize, Serialize};
     use serde_test::Token;
     use static_assertions::{assert_fields, assert_impl_all};
@@ -157,6 +169,7 @@ mod tests {
 
     assert_fields!(
         Role: color,
+        colors,
         hoist,
         icon,
         id,
@@ -183,6 +196,7 @@ mod tests {
     fn role() {
         let role = Role {
             color: 0,
+            colors: RoleColors::solid(0),
             hoist: true,
             icon: None,
             id: Id::new(123),
@@ -201,10 +215,18 @@ mod tests {
             &[
                 Token::Struct {
                     name: "Role",
-                    len: 9,
+                    len: 10,
                 },
                 Token::Str("color"),
                 Token::U32(0),
+                Token::Str("colors"),
+                Token::Struct {
+                    name: "RoleColors",
+                    len: 1,
+                },
+                Token::Str("primary_color"),
+                Token::U32(0),
+                Token::StructEnd,
                 Token::Str("hoist"),
                 Token::Bool(true),
                 Token::Str("id"),
@@ -226,4 +248,22 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn role_without_colors() {
+        let role: Role = serde_json::from_value(serde_json::json!({
+            "color": 0,
+            "hoist": false,
+            "id": "123",
+            "managed": false,
+            "mentionable": false,
+            "name": "test",
+            "permissions": "0",
+            "position": 0,
+            "flags": 0,
+        }))
+        .unwrap();
+
+        assert_eq!(role.colors, RoleColors::default());
+    }
 }
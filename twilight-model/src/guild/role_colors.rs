@@ -0,0 +1,485 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Parsing [`RoleColors`] from hex strings failed.
+#[derive(Debug)]
+pub struct RoleColorsParseError {
+    kind: RoleColorsParseErrorType,
+}
+
+impl RoleColorsParseError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &RoleColorsParseErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[allow(clippy::unused_self)]
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        None
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        RoleColorsParseErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, None)
+    }
+}
+
+impl Display for RoleColorsParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            RoleColorsParseErrorType::InvalidHex { color } => {
+                f.write_str("color `")?;
+                f.write_str(color)?;
+
+                f.write_str("` is not a 6 digit hex color")
+            }
+        }
+    }
+}
+
+impl Error for RoleColorsParseError {}
+
+/// Type of [`RoleColorsParseError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RoleColorsParseErrorType {
+    /// Color is not in the `#RRGGBB` or `RRGGBB` format.
+    InvalidHex {
+        /// Provided color string.
+        color: String,
+    },
+}
+
+/// Colors of a [`Role`].
+///
+/// A role with only a [`primary_color`] is displayed as a solid color. Setting
+/// a [`secondary_color`] displays the role as a gradient between the two
+/// colors, and additionally setting a [`tertiary_color`] displays it in the
+/// holographic style.
+///
+/// Each color is an RGB integer and may not exceed `0xFFFFFF`. A color of `0`
+/// means the role has no color. Colors in the `0xAARRGGBB` format may be
+/// accepted by deserializing with [`deserialize_strip_alpha`], which drops
+/// their alpha bits.
+///
+/// The secondary and tertiary colors may only be set when the guild has the
+/// [`GuildFeature::EnhancedRoleColors`] feature. A holographic role
+/// additionally requires all three colors to be set to Discord's holographic
+/// values, which [`holographic`] fills in:
+///
+/// | Field             | Value      |
+/// | ----------------- | ---------- |
+/// | `primary_color`   | `11127295` |
+/// | `secondary_color` | `16759788` |
+/// | `tertiary_color`  | `16761760` |
+///
+/// [`GuildFeature::EnhancedRoleColors`]: super::GuildFeature::EnhancedRoleColors
+/// [`Role`]: super::Role
+/// [`deserialize_strip_alpha`]: Self::deserialize_strip_alpha
+/// [`holographic`]: Self::holographic
+/// [`primary_color`]: Self::primary_color
+/// [`secondary_color`]: Self::secondary_color
+/// [`tertiary_color`]: Self::tertiary_color
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct RoleColors {
+    /// Primary color of the role.
+    pub primary_color: u32,
+    /// Secondary color of the role, displaying it as a gradient.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_color: Option<u32>,
+    /// Tertiary color of the role, displaying it in the holographic style.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tertiary_color: Option<u32>,
+}
+
+impl RoleColors {
+    /// Mask retaining the RGB bytes of a color.
+    const RGB_MASK: u32 = 0xFF_FF_FF;
+
+    /// Create colors displaying the role as a solid color.
+    pub const fn solid(primary_color: u32) -> Self {
+        Self {
+            primary_color,
+            secondary_color: None,
+            tertiary_color: None,
+        }
+    }
+
+    /// Create colors displaying the role as a gradient between two colors.
+    pub const fn gradient(primary_color: u32, secondary_color: u32) -> Self {
+        Self {
+            primary_color,
+            secondary_color: Some(secondary_color),
+            tertiary_color: None,
+        }
+    }
+
+    /// Create colors displaying the role in the holographic style.
+    pub const fn holographic() -> Self {
+        Self {
+            primary_color: 11_127_295,
+            secondary_color: Some(16_759_788),
+            tertiary_color: Some(16_761_760),
+        }
+    }
+
+    /// Parse role colors from hex strings in the `#RRGGBB` or `RRGGBB` format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_model::guild::RoleColors;
+    ///
+    /// let colors = RoleColors::from_hex("#A9D6FF", Some("FFCDA9"), None)?;
+    /// assert_eq!(colors, RoleColors::gradient(0xA9_D6_FF, 0xFF_CD_A9));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of type [`InvalidHex`] if any of the colors isn't a 6
+    /// digit hex color.
+    ///
+    /// [`InvalidHex`]: RoleColorsParseErrorType::InvalidHex
+    pub fn from_hex(
+        primary_color: &str,
+        secondary_color: Option<&str>,
+        tertiary_color: Option<&str>,
+    ) -> Result<Self, RoleColorsParseError> {
+        Ok(Self {
+            primary_color: Self::parse_hex(primary_color)?,
+            secondary_color: secondary_color.map(Self::parse_hex).transpose()?,
+            tertiary_color: tertiary_color.map(Self::parse_hex).transpose()?,
+        })
+    }
+
+    /// Format the primary, secondary, and tertiary colors as hex strings in
+    /// the `#RRGGBB` format.
+    ///
+    /// This is the inverse of [`from_hex`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_model::guild::RoleColors;
+    ///
+    /// let colors = RoleColors::gradient(0xA9_D6_FF, 0xFF_CD_A9);
+    /// assert_eq!(
+    ///     colors.to_hex(),
+    ///     ("#A9D6FF".to_owned(), Some("#FFCDA9".to_owned()), None)
+    /// );
+    /// ```
+    ///
+    /// [`from_hex`]: Self::from_hex
+    pub fn to_hex(&self) -> (String, Option<String>, Option<String>) {
+        (
+            Self::format_hex(self.primary_color),
+            self.secondary_color.map(Self::format_hex),
+            self.tertiary_color.map(Self::format_hex),
+        )
+    }
+
+    /// Format a single color in the `#RRGGBB` format.
+    fn format_hex(color: u32) -> String {
+        format!("#{color:06X}")
+    }
+
+    /// Parse a single color in the `#RRGGBB` or `RRGGBB` format.
+    fn parse_hex(color: &str) -> Result<u32, RoleColorsParseError> {
+        let digits = color.strip_prefix('#').unwrap_or(color);
+
+        if digits.len() != 6 || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(RoleColorsParseError {
+                kind: RoleColorsParseErrorType::InvalidHex {
+                    color: color.to_owned(),
+                },
+            });
+        }
+
+        Ok(u32::from_str_radix(digits, 16).expect("validated as 6 hex digits"))
+    }
+
+    /// Deserialize role colors, stripping the alpha byte of colors in the
+    /// `0xAARRGGBB` format.
+    ///
+    /// Role colors are RGB only, but some sources provide colors with an alpha
+    /// byte. Rather than keeping a color larger than `0xFFFFFF`, which Discord
+    /// would reject, the alpha byte is discarded. This is silent: no warning
+    /// is emitted, so compare the input against `0xFFFFFF` beforehand if
+    /// discarded alpha bytes need to be reported. Serialization is unaffected
+    /// and always produces the RGB value.
+    ///
+    /// This is opt-in and meant to be used via
+    /// `#[serde(deserialize_with = "RoleColors::deserialize_strip_alpha")]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the input isn't valid role colors.
+    pub fn deserialize_strip_alpha<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let colors = Self::deserialize(deserializer)?;
+
+        Ok(Self {
+            primary_color: colors.primary_color & Self::RGB_MASK,
+            secondary_color: colors.secondary_color.map(|color| color & Self::RGB_MASK),
+            tertiary_color: colors.tertiary_color.map(|color| color & Self::RGB_MASK),
+        })
+    }
+}
+
+/// Display the style of the role colors.
+///
+/// Solid colors are displayed as `solid #A9D6FF`, gradients as
+/// `gradient #A9D6FF → #FFCDA9`, and [`holographic`] roles as `holographic`.
+/// Any other colors with a tertiary color are displayed as a gradient of all
+/// three colors. Roles without a color are displayed as `none`.
+///
+/// [`holographic`]: RoleColors::holographic
+impl Display for RoleColors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if *self == Self::holographic() {
+            return f.write_str("holographic");
+        }
+
+        match (
+            self.primary_color,
+            self.secondary_color,
+            self.tertiary_color,
+        ) {
+            (0, None, None) => f.write_str("none"),
+            (primary, None, None) => {
+                f.write_str("solid ")?;
+
+                f.write_str(&Self::format_hex(primary))
+            }
+            (primary, secondary, tertiary) => {
+                f.write_str("gradient ")?;
+                f.write_str(&Self::format_hex(primary))?;
+
+                for color in [secondary, tertiary].into_iter().flatten() {
+                    f.write_str(" \u{2192} ")?;
+                    f.write_str(&Self::format_hex(color))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RoleColors, RoleColorsParseErrorType};
+    use serde::{Deserialize, Serialize};
+    use serde_test::Token;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+
+    assert_fields!(RoleColors: primary_color, secondary_color, tertiary_color);
+
+    assert_impl_all!(
+        RoleColors: Clone,
+        Debug,
+        Deserialize<'static>,
+        Display,
+        Eq,
+        Hash,
+        PartialEq,
+        Serialize
+    );
+
+    #[test]
+    fn gradient() {
+        let value = RoleColors {
+            primary_color: 0xFF_00_00,
+            secondary_color: Some(0x00_00_FF),
+            tertiary_color: None,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "RoleColors",
+                    len: 2,
+                },
+                Token::Str("primary_color"),
+                Token::U32(0xFF_00_00),
+                Token::Str("secondary_color"),
+                Token::Some,
+                Token::U32(0x00_00_FF),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn solid() {
+        let value = RoleColors::solid(0xA9_D6_FF);
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "RoleColors",
+                    len: 1,
+                },
+                Token::Str("primary_color"),
+                Token::U32(0xA9_D6_FF),
+                Token::StructEnd,
+            ],
+        );
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"primary_color":11130623}"#
+        );
+    }
+
+    #[test]
+    fn holographic() {
+        let value = RoleColors::holographic();
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "RoleColors",
+                    len: 3,
+                },
+                Token::Str("primary_color"),
+                Token::U32(11_127_295),
+                Token::Str("secondary_color"),
+                Token::Some,
+                Token::U32(16_759_788),
+                Token::Str("tertiary_color"),
+                Token::Some,
+                Token::U32(16_761_760),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn constructors() {
+        assert_eq!(
+            RoleColors::solid(0xA9_D6_FF),
+            RoleColors {
+                primary_color: 0xA9_D6_FF,
+                secondary_color: None,
+                tertiary_color: None,
+            }
+        );
+        assert_eq!(
+            RoleColors::gradient(0xA9_D6_FF, 0xFF_CD_A9),
+            RoleColors {
+                primary_color: 0xA9_D6_FF,
+                secondary_color: Some(0xFF_CD_A9),
+                tertiary_color: None,
+            }
+        );
+    }
+
+    #[test]
+    fn hex() {
+        let colors = RoleColors::from_hex("#A9D6FF", Some("ffcda9"), Some("#000000")).unwrap();
+        assert_eq!(
+            colors,
+            RoleColors {
+                primary_color: 0xA9_D6_FF,
+                secondary_color: Some(0xFF_CD_A9),
+                tertiary_color: Some(0),
+            }
+        );
+
+        assert_eq!(
+            colors.to_hex(),
+            (
+                "#A9D6FF".to_owned(),
+                Some("#FFCDA9".to_owned()),
+                Some("#000000".to_owned())
+            )
+        );
+    }
+
+    #[test]
+    fn hex_invalid() {
+        for color in [
+            "",
+            "#",
+            "#FFF",
+            "A9D6FF0",
+            "#FFA9D6FF",
+            "#GGGGGG",
+            "+A9D6F",
+            "#A9 6FF",
+        ] {
+            let error = RoleColors::from_hex(color, None, None).unwrap_err();
+            assert!(matches!(
+                error.kind(),
+                RoleColorsParseErrorType::InvalidHex { color: invalid } if invalid == color
+            ));
+        }
+
+        assert!(RoleColors::from_hex("#A9D6FF", Some("#A9D6FF"), Some("FFF")).is_err());
+    }
+
+    #[test]
+    fn deserialize_strip_alpha() {
+        let input = r#"{
+            "primary_color": 4289320703,
+            "secondary_color": 16759788,
+            "tertiary_color": null
+        }"#;
+
+        let value =
+            RoleColors::deserialize_strip_alpha(&mut serde_json::Deserializer::from_str(input))
+                .unwrap();
+
+        assert_eq!(
+            value,
+            RoleColors {
+                primary_color: 0xA9_D6_FF,
+                secondary_color: Some(16_759_788),
+                tertiary_color: None,
+            }
+        );
+    }
+
+    #[test]
+    fn display() {
+        let colors = |primary_color, secondary_color, tertiary_color| RoleColors {
+            primary_color,
+            secondary_color,
+            tertiary_color,
+        };
+
+        assert_eq!(colors(0, None, None).to_string(), "none");
+        assert_eq!(colors(0xA9_D6_FF, None, None).to_string(), "solid #A9D6FF");
+        assert_eq!(colors(0xFF, None, None).to_string(), "solid #0000FF");
+        assert_eq!(
+            colors(0xA9_D6_FF, Some(0xFF_CD_A9), None).to_string(),
+            "gradient #A9D6FF \u{2192} #FFCDA9"
+        );
+        assert_eq!(RoleColors::holographic().to_string(), "holographic");
+        assert_eq!(
+            colors(0xA9_D6_FF, Some(0xFF_CD_A9), Some(0xFF_FF_FF)).to_string(),
+            "gradient #A9D6FF \u{2192} #FFCDA9 \u{2192} #FFFFFF"
+        );
+    }
+}
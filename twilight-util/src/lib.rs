@@ -22,5 +22,8 @@ pub mod link;
 #[cfg(feature = "permission-calculator")]
 pub mod permission_calculator;
 
+#[cfg(feature = "role-color")]
+pub mod role_color;
+
 #[cfg(feature = "snowflake")]
 pub mod snowflake;
@@ -0,0 +1,448 @@
+//! Resolve the color a member is displayed with from their roles.
+//!
+//! Discord displays a member's name in the color of their highest role that
+//! has a color. Colors of `0` mean no color, so roles whose colors are all `0`
+//! are skipped, and a member whose roles are all uncolored has no display
+//! color. Gradient and holographic roles count as colored, and are displayed
+//! with all of their colors.
+//!
+//! Bots that repeatedly resolve colors for the same sets of roles may memoize
+//! the results with a [`RoleColorCache`].
+
+use std::collections::HashMap;
+use twilight_model::{
+    gateway::event::Event,
+    guild::{Role, RoleColors},
+    id::{
+        marker::{GuildMarker, RoleMarker},
+        Id,
+    },
+};
+
+/// Sorted IDs of the roles that colors were calculated from.
+type RoleSet = Box<[Id<RoleMarker>]>;
+
+/// Memoized [`effective_member_color`] results, keyed by guild and the set of
+/// role IDs.
+///
+/// Results are kept until one of their roles is updated or deleted, or their
+/// guild is deleted, which the cache learns about by being [updated] with
+/// gateway events.
+///
+/// Members changing roles leave results for role sets that may no longer be
+/// used, so each guild keeps at most a [maximum number of role sets]. Once
+/// reached, one of the guild's results, chosen arbitrarily, is evicted before
+/// caching another.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_model::{gateway::event::Event, guild::Role, id::Id};
+/// use twilight_util::role_color::RoleColorCache;
+///
+/// # fn roles() -> Vec<Role> { Vec::new() }
+/// # fn events() -> Vec<Event> { Vec::new() }
+/// let mut cache = RoleColorCache::new();
+///
+/// for event in events() {
+///     cache.update(&event);
+/// }
+///
+/// let member_roles: Vec<Role> = roles();
+/// let colors = cache.effective_member_color(Id::new(1), &member_roles);
+/// ```
+///
+/// [maximum number of role sets]: Self::with_max_role_sets
+/// [updated]: Self::update
+#[derive(Clone, Debug)]
+pub struct RoleColorCache {
+    /// Calculated colors by guild.
+    guilds: HashMap<Id<GuildMarker>, HashMap<RoleSet, Option<RoleColors>>>,
+    /// Maximum number of role sets to keep results for per guild.
+    max_role_sets: usize,
+}
+
+impl RoleColorCache {
+    /// Default maximum number of role sets to keep results for per guild.
+    pub const DEFAULT_MAX_ROLE_SETS: usize = 1000;
+
+    /// Create a new, empty cache keeping results for up to
+    /// [`DEFAULT_MAX_ROLE_SETS`] role sets per guild.
+    ///
+    /// [`DEFAULT_MAX_ROLE_SETS`]: Self::DEFAULT_MAX_ROLE_SETS
+    pub fn new() -> Self {
+        Self::with_max_role_sets(Self::DEFAULT_MAX_ROLE_SETS)
+    }
+
+    /// Create a new, empty cache keeping results for up to the given number
+    /// of role sets per guild.
+    ///
+    /// At least one result is always kept.
+    pub fn with_max_role_sets(max_role_sets: usize) -> Self {
+        Self {
+            guilds: HashMap::new(),
+            max_role_sets,
+        }
+    }
+
+    /// Calculate the colors a member of a guild is displayed with from their
+    /// roles, reusing the previous result for the same set of roles.
+    ///
+    /// Refer to the [`effective_member_color`] function for how the colors are
+    /// calculated.
+    pub fn effective_member_color(
+        &mut self,
+        guild_id: Id<GuildMarker>,
+        roles: &[Role],
+    ) -> Option<RoleColors> {
+        let mut key = roles.iter().map(|role| role.id).collect::<Vec<_>>();
+        key.sort_unstable();
+        key.dedup();
+
+        let colors = self.guilds.entry(guild_id).or_default();
+        if colors.len() >= self.max_role_sets && !colors.contains_key(key.as_slice()) {
+            if let Some(evicted) = colors.keys().next().cloned() {
+                colors.remove(&evicted);
+            }
+        }
+
+        colors
+            .entry(key.into_boxed_slice())
+            .or_insert_with(|| effective_member_color(roles))
+            .clone()
+    }
+
+    /// Remove the results calculated from a role.
+    pub fn invalidate_role(&mut self, guild_id: Id<GuildMarker>, role_id: Id<RoleMarker>) {
+        if let Some(colors) = self.guilds.get_mut(&guild_id) {
+            colors.retain(|role_ids, _| role_ids.binary_search(&role_id).is_err());
+
+            if colors.is_empty() {
+                self.guilds.remove(&guild_id);
+            }
+        }
+    }
+
+    /// Remove the results calculated for a guild.
+    pub fn remove_guild(&mut self, guild_id: Id<GuildMarker>) {
+        self.guilds.remove(&guild_id);
+    }
+
+    /// Update the cache with an event from the gateway.
+    ///
+    /// Results calculated from updated or deleted roles, or for deleted
+    /// guilds, are invalidated.
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::GuildDelete(guild_delete) => self.remove_guild(guild_delete.id),
+            Event::RoleDelete(role_delete) => {
+                self.invalidate_role(role_delete.guild_id, role_delete.role_id);
+            }
+            Event::RoleUpdate(role_update) => {
+                self.invalidate_role(role_update.guild_id, role_update.role.id);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for RoleColorCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calculate the colors a member is displayed with from their roles.
+///
+/// Returns the [`RoleColors`] of the highest positioned role with a non-zero
+/// color, using [`Role`]'s ordering to break ties between roles with
+/// the same position. Returns [`None`] if none of the roles have a color.
+///
+/// The roles don't need to be sorted.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_model::guild::Role;
+/// use twilight_util::role_color;
+///
+/// # fn roles() -> Vec<Role> { Vec::new() }
+/// let member_roles: Vec<Role> = roles();
+///
+/// match role_color::effective_member_color(&member_roles) {
+///     Some(colors) => println!("displayed in #{:06X}", colors.primary_color),
+///     None => println!("displayed in the default color"),
+/// }
+/// ```
+pub fn effective_member_color(roles: &[Role]) -> Option<RoleColors> {
+    roles
+        .iter()
+        .filter(|role| has_color(&role.colors))
+        .max()
+        .map(|role| role.colors.clone())
+}
+
+/// Whether any of the role's colors is set.
+fn has_color(colors: &RoleColors) -> bool {
+    colors.primary_color != 0
+        || colors.secondary_color.is_some_and(|color| color != 0)
+        || colors.tertiary_color.is_some_and(|color| color != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{effective_member_color, RoleColorCache};
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        gateway::{
+            event::Event,
+            payload::incoming::{GuildDelete, RoleDelete, RoleUpdate},
+        },
+        guild::{Permissions, Role, RoleColors, RoleFlags},
+        id::{marker::GuildMarker, Id},
+    };
+
+    assert_impl_all!(RoleColorCache: Clone, Debug, Default, Send, Sync);
+
+    const GUILD_ID: Id<GuildMarker> = Id::new(1);
+
+    fn role(id: u64, position: i64, primary_color: u32) -> Role {
+        role_with_colors(id, position, RoleColors::solid(primary_color))
+    }
+
+    fn role_with_colors(id: u64, position: i64, colors: RoleColors) -> Role {
+        Role {
+            color: colors.primary_color,
+            colors,
+            hoist: false,
+            icon: None,
+            id: Id::new(id),
+            managed: false,
+            mentionable: false,
+            name: String::new(),
+            permissions: Permissions::empty(),
+            position,
+            flags: RoleFlags::empty(),
+            tags: None,
+            unicode_emoji: None,
+        }
+    }
+
+    #[test]
+    fn no_roles() {
+        assert!(effective_member_color(&[]).is_none());
+    }
+
+    #[test]
+    fn no_colored_roles() {
+        let roles = [role(1, 0, 0), role(2, 5, 0)];
+
+        assert!(effective_member_color(&roles).is_none());
+    }
+
+    #[test]
+    fn highest_colored_role() {
+        let roles = [
+            role(1, 1, 0xFF_00_00),
+            role(2, 3, 0x00_FF_00),
+            role(3, 2, 0x00_00_FF),
+        ];
+
+        assert_eq!(
+            Some(0x00_FF_00),
+            effective_member_color(&roles).map(|colors| colors.primary_color)
+        );
+    }
+
+    #[test]
+    fn uncolored_role_above_colored_role() {
+        let roles = [
+            role(1, 10, 0),
+            role(2, 4, 0xA9_D6_FF),
+            role(3, 2, 0xFF_CD_A9),
+        ];
+
+        assert_eq!(
+            Some(0xA9_D6_FF),
+            effective_member_color(&roles).map(|colors| colors.primary_color)
+        );
+    }
+
+    #[test]
+    fn same_position_lowest_id_wins() {
+        let roles = [role(20, 3, 0xFF_00_00), role(10, 3, 0x00_FF_00)];
+
+        assert_eq!(
+            Some(0x00_FF_00),
+            effective_member_color(&roles).map(|colors| colors.primary_color)
+        );
+    }
+
+    #[test]
+    fn gradient_above_solid() {
+        let gradient = RoleColors {
+            primary_color: 0xFF_00_00,
+            secondary_color: Some(0x00_00_FF),
+            tertiary_color: None,
+        };
+        let roles = [
+            role(1, 2, 0x00_FF_00),
+            role_with_colors(2, 5, gradient.clone()),
+        ];
+
+        assert_eq!(Some(gradient), effective_member_color(&roles));
+    }
+
+    #[test]
+    fn holographic_below_solid() {
+        let roles = [
+            role_with_colors(1, 1, RoleColors::holographic()),
+            role(2, 4, 0x00_FF_00),
+        ];
+
+        assert_eq!(
+            Some(0x00_FF_00),
+            effective_member_color(&roles).map(|colors| colors.primary_color)
+        );
+
+        let roles = [
+            role_with_colors(1, 6, RoleColors::holographic()),
+            role(2, 4, 0x00_FF_00),
+        ];
+
+        assert_eq!(
+            Some(RoleColors::holographic()),
+            effective_member_color(&roles)
+        );
+    }
+
+    #[test]
+    fn gradient_without_primary_color() {
+        let gradient = RoleColors {
+            primary_color: 0,
+            secondary_color: Some(0x00_00_FF),
+            tertiary_color: None,
+        };
+        let roles = [
+            role(1, 1, 0xFF_00_00),
+            role_with_colors(2, 3, gradient.clone()),
+        ];
+
+        assert_eq!(Some(gradient), effective_member_color(&roles));
+    }
+
+    #[test]
+    fn cache_hit() {
+        let mut cache = RoleColorCache::new();
+        let roles = [role(1, 1, 0xFF_00_00), role(2, 2, 0x00_FF_00)];
+
+        assert_eq!(
+            Some(0x00_FF_00),
+            cache
+                .effective_member_color(GUILD_ID, &roles)
+                .map(|colors| colors.primary_color)
+        );
+
+        // The same set of roles in another order hits the cache, even though
+        // the roles changed without the cache being updated.
+        let changed = [role(2, 2, 0x00_00_FF), role(1, 1, 0xFF_00_00)];
+        assert_eq!(
+            Some(0x00_FF_00),
+            cache
+                .effective_member_color(GUILD_ID, &changed)
+                .map(|colors| colors.primary_color)
+        );
+
+        // A different set of roles doesn't.
+        assert_eq!(
+            Some(0xFF_00_00),
+            cache
+                .effective_member_color(GUILD_ID, &changed[1..])
+                .map(|colors| colors.primary_color)
+        );
+    }
+
+    #[test]
+    fn cache_invalidation() {
+        let mut cache = RoleColorCache::new();
+        let roles = [role(1, 1, 0xFF_00_00), role(2, 2, 0x00_FF_00)];
+        cache.effective_member_color(GUILD_ID, &roles);
+        cache.effective_member_color(GUILD_ID, &roles[..1]);
+
+        let updated = role(2, 2, 0x00_00_FF);
+        cache.update(&Event::RoleUpdate(RoleUpdate {
+            guild_id: GUILD_ID,
+            role: updated.clone(),
+        }));
+
+        let roles = [role(1, 1, 0xFF_00_00), updated];
+        assert_eq!(
+            Some(0x00_00_FF),
+            cache
+                .effective_member_color(GUILD_ID, &roles)
+                .map(|colors| colors.primary_color)
+        );
+
+        // Results not calculated from the updated role are kept.
+        assert_eq!(2, cache.guilds[&GUILD_ID].len());
+
+        cache.update(&Event::RoleDelete(RoleDelete {
+            guild_id: GUILD_ID,
+            role_id: Id::new(1),
+        }));
+        assert!(cache.guilds.is_empty());
+    }
+
+    #[test]
+    fn cache_guild_delete() {
+        const OTHER_GUILD_ID: Id<GuildMarker> = Id::new(2);
+
+        let mut cache = RoleColorCache::new();
+        let roles = [role(1, 1, 0xFF_00_00)];
+        cache.effective_member_color(GUILD_ID, &roles);
+        cache.effective_member_color(OTHER_GUILD_ID, &roles);
+
+        // Role updates only affect their guild.
+        cache.update(&Event::RoleUpdate(RoleUpdate {
+            guild_id: OTHER_GUILD_ID,
+            role: role(1, 1, 0x00_FF_00),
+        }));
+        assert!(cache.guilds.contains_key(&GUILD_ID));
+        assert!(!cache.guilds.contains_key(&OTHER_GUILD_ID));
+
+        cache.update(&Event::GuildDelete(GuildDelete {
+            id: GUILD_ID,
+            unavailable: None,
+        }));
+        assert!(cache.guilds.is_empty());
+    }
+
+    #[test]
+    fn cache_max_role_sets() {
+        let mut cache = RoleColorCache::with_max_role_sets(2);
+        let roles = [
+            role(1, 1, 0xFF_00_00),
+            role(2, 2, 0x00_FF_00),
+            role(3, 3, 0x00_00_FF),
+        ];
+
+        // A member gaining roles leaves the result for their previous roles.
+        cache.effective_member_color(GUILD_ID, &roles[..1]);
+        cache.effective_member_color(GUILD_ID, &roles[..2]);
+        assert_eq!(2, cache.guilds[&GUILD_ID].len());
+
+        // Cached role sets don't count towards the maximum.
+        cache.effective_member_color(GUILD_ID, &roles[..1]);
+        assert_eq!(2, cache.guilds[&GUILD_ID].len());
+
+        assert_eq!(
+            Some(0x00_00_FF),
+            cache
+                .effective_member_color(GUILD_ID, &roles)
+                .map(|colors| colors.primary_color)
+        );
+        assert_eq!(2, cache.guilds[&GUILD_ID].len());
+        assert!(cache.guilds[&GUILD_ID].contains_key(&[Id::new(1), Id::new(2), Id::new(3)][..]));
+    }
+}
@@ -0,0 +1,266 @@
+//! Constants, error types, and functions for validating [`Role`] fields.
+//!
+//! [`Role`]: twilight_model::guild::Role
+
+pub use crate::embed::COLOR_MAXIMUM;
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::guild::{Role, RoleColors};
+
+/// Error created if validation of a role field fails.
+#[derive(Debug)]
+pub struct RoleValidationError {
+    /// Type of error that occurred.
+    kind: RoleValidationErrorType,
+}
+
+impl RoleValidationError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &RoleValidationErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[allow(clippy::unused_self)]
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        None
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        RoleValidationErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, None)
+    }
+}
+
+impl Display for RoleValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            RoleValidationErrorType::ColorNotRgb { color } => {
+                f.write_str("the color is ")?;
+                Display::fmt(color, f)?;
+                f.write_str(", but it must be less than or equal to ")?;
+
+                Display::fmt(&COLOR_MAXIMUM, f)
+            }
+            RoleValidationErrorType::MissingPrimaryColor => {
+                f.write_str("a secondary or tertiary color requires a primary color")
+            }
+            RoleValidationErrorType::MissingSecondaryColor => {
+                f.write_str("a tertiary color requires a secondary color")
+            }
+            RoleValidationErrorType::RoleColorsInvalid { idx, .. } => {
+                f.write_str("colors of the role at index ")?;
+                Display::fmt(idx, f)?;
+
+                f.write_str(" are invalid")
+            }
+        }
+    }
+}
+
+impl Error for RoleValidationError {}
+
+/// Type of [`RoleValidationError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RoleValidationErrorType {
+    /// Color is larger than a valid RGB hexadecimal value.
+    ColorNotRgb {
+        /// Provided color hex value.
+        color: u32,
+    },
+    /// Secondary or tertiary color is set without a primary color.
+    MissingPrimaryColor,
+    /// Tertiary color is set without a secondary color.
+    MissingSecondaryColor,
+    /// Colors of a role in a list of roles are invalid.
+    RoleColorsInvalid {
+        /// Index of the role.
+        idx: usize,
+        /// Additional details about the validation failure type.
+        kind: Box<RoleValidationErrorType>,
+    },
+}
+
+/// Ensure that a role's colors are correct.
+///
+/// Each of the colors must be at most [`COLOR_MAXIMUM`]. A secondary color
+/// requires a primary color, and a tertiary color requires both.
+///
+/// # Errors
+///
+/// Returns an error of type [`ColorNotRgb`] if any of the colors is not a valid
+/// RGB integer.
+///
+/// Returns an error of type [`MissingPrimaryColor`] if the primary color is `0`
+/// while a secondary or tertiary color is set.
+///
+/// Returns an error of type [`MissingSecondaryColor`] if a tertiary color is
+/// set without a secondary color.
+///
+/// [`ColorNotRgb`]: RoleValidationErrorType::ColorNotRgb
+/// [`MissingPrimaryColor`]: RoleValidationErrorType::MissingPrimaryColor
+/// [`MissingSecondaryColor`]: RoleValidationErrorType::MissingSecondaryColor
+pub fn colors(colors: &RoleColors) -> Result<(), RoleValidationError> {
+    if colors.tertiary_color.is_some() && colors.secondary_color.is_none() {
+        return Err(RoleValidationError {
+            kind: RoleValidationErrorType::MissingSecondaryColor,
+        });
+    }
+
+    if colors.primary_color == 0
+        && (colors.secondary_color.is_some() || colors.tertiary_color.is_some())
+    {
+        return Err(RoleValidationError {
+            kind: RoleValidationErrorType::MissingPrimaryColor,
+        });
+    }
+
+    let colors = [
+        Some(colors.primary_color),
+        colors.secondary_color,
+        colors.tertiary_color,
+    ];
+
+    if let Some(color) = colors
+        .into_iter()
+        .flatten()
+        .find(|&color| color > COLOR_MAXIMUM)
+    {
+        return Err(RoleValidationError {
+            kind: RoleValidationErrorType::ColorNotRgb { color },
+        });
+    }
+
+    Ok(())
+}
+
+/// Ensure that the colors of every role are correct.
+///
+/// Refer to [`colors`] for the validation of each role's colors.
+///
+/// # Errors
+///
+/// Returns an error of type [`RoleColorsInvalid`] for the first role whose
+/// colors are invalid.
+///
+/// [`RoleColorsInvalid`]: RoleValidationErrorType::RoleColorsInvalid
+pub fn roles_colors(roles: &[Role]) -> Result<(), RoleValidationError> {
+    for (idx, role) in roles.iter().enumerate() {
+        colors(&role.colors).map_err(|source| RoleValidationError {
+            kind: RoleValidationErrorType::RoleColorsInvalid {
+                idx,
+                kind: Box::new(source.kind),
+            },
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twilight_model::{
+        guild::{Permissions, RoleFlags},
+        id::Id,
+    };
+
+    const fn role_colors(
+        primary_color: u32,
+        secondary_color: Option<u32>,
+        tertiary_color: Option<u32>,
+    ) -> RoleColors {
+        RoleColors {
+            primary_color,
+            secondary_color,
+            tertiary_color,
+        }
+    }
+
+    fn role(colors: RoleColors) -> Role {
+        Role {
+            color: colors.primary_color,
+            colors,
+            hoist: false,
+            icon: None,
+            id: Id::new(1),
+            managed: false,
+            mentionable: false,
+            name: "role".to_owned(),
+            permissions: Permissions::empty(),
+            position: 0,
+            flags: RoleFlags::empty(),
+            tags: None,
+            unicode_emoji: None,
+        }
+    }
+
+    #[test]
+    fn colors_maximum() {
+        assert!(colors(&role_colors(0, None, None)).is_ok());
+        assert!(colors(&role_colors(COLOR_MAXIMUM, None, None)).is_ok());
+        assert!(colors(&role_colors(1, Some(COLOR_MAXIMUM), Some(COLOR_MAXIMUM))).is_ok());
+
+        assert!(matches!(
+            colors(&role_colors(COLOR_MAXIMUM + 1, None, None)).unwrap_err().kind(),
+            RoleValidationErrorType::ColorNotRgb { color } if *color == COLOR_MAXIMUM + 1
+        ));
+        assert!(colors(&role_colors(1, Some(COLOR_MAXIMUM + 1), None)).is_err());
+        assert!(colors(&role_colors(1, Some(2), Some(COLOR_MAXIMUM + 1))).is_err());
+    }
+
+    #[test]
+    fn colors_styles() {
+        assert!(colors(&role_colors(0xa9_d6_ff, Some(0xff_cd_a9), None)).is_ok());
+        assert!(colors(&role_colors(11_127_295, Some(16_759_788), Some(16_761_760))).is_ok());
+
+        assert!(matches!(
+            colors(&role_colors(0xa9_d6_ff, None, Some(0xff_cd_a9)))
+                .unwrap_err()
+                .kind(),
+            RoleValidationErrorType::MissingSecondaryColor
+        ));
+        assert!(matches!(
+            colors(&role_colors(0, Some(0xff_cd_a9), None))
+                .unwrap_err()
+                .kind(),
+            RoleValidationErrorType::MissingPrimaryColor
+        ));
+    }
+
+    #[test]
+    fn roles_colors_reports_first_invalid_role() {
+        let roles = [
+            role(role_colors(0xa9_d6_ff, None, None)),
+            role(role_colors(0xff_a9_d6_ff, None, None)),
+            role(role_colors(0xa9_d6_ff, Some(0xff_cd_a9), None)),
+            role(role_colors(0xa9_d6_ff, Some(0xff_ff_cd_a9), None)),
+        ];
+
+        assert!(roles_colors(&roles[..1]).is_ok());
+        assert!(roles_colors(&[]).is_ok());
+
+        assert!(matches!(
+            roles_colors(&roles).unwrap_err().kind(),
+            RoleValidationErrorType::RoleColorsInvalid { idx: 1, kind }
+                if matches!(**kind, RoleValidationErrorType::ColorNotRgb { color } if color == 0xff_a9_d6_ff)
+        ));
+        assert!(matches!(
+            roles_colors(&roles[2..]).unwrap_err().kind(),
+            RoleValidationErrorType::RoleColorsInvalid { idx: 1, kind }
+                if matches!(**kind, RoleValidationErrorType::ColorNotRgb { color } if color == 0xff_ff_cd_a9)
+        ));
+    }
+}